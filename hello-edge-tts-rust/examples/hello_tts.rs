@@ -88,7 +88,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             if let Some(voice) = en_voices.first() {
                 println!("🎤 Using voice: {} ({})", voice.display_name, voice.name);
 
-                let demo_texts = vec![
+                let demo_texts = [
                     "Hello, World!",
                     "This is a demonstration of Edge TTS with Rust.",
                     "The quick brown fox jumps over the lazy dog.",
@@ -167,6 +167,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         ssml: false,
         batch_size: 5,
         max_concurrent: 3,
+        ..TTSConfig::default()
     };
 
     let _custom_client = TTSClient::new(Some(custom_config));
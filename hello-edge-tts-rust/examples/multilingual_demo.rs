@@ -20,20 +20,24 @@ struct Config {
     languages: Vec<LanguageConfig>,
 }
 
-/// Load language configuration from JSON file
+/// Copy of the shared language table embedded at compile time, so the
+/// demo runs from any working directory
+const EMBEDDED_CONFIG: &str = include_str!("../src/multilingual_demo_config.json");
+
+/// Load language configuration from the shared JSON file, falling back to
+/// the embedded copy when it is not reachable from here
 fn load_language_config() -> Result<Vec<LanguageConfig>, Box<dyn std::error::Error>> {
     let config_path = "../shared/multilingual_demo_config.json";
-    
-    match fs::read_to_string(config_path) {
-        Ok(content) => {
-            let config: Config = serde_json::from_str(&content)?;
-            Ok(config.languages)
-        }
-        Err(e) => {
-            eprintln!("❌ Configuration file not found: {}", config_path);
-            Err(Box::new(e))
+
+    let content = match fs::read_to_string(config_path) {
+        Ok(content) => content,
+        Err(_) => {
+            println!("ℹ️  {} not found, using the embedded table", config_path);
+            EMBEDDED_CONFIG.to_string()
         }
-    }
+    };
+    let config: Config = serde_json::from_str(&content)?;
+    Ok(config.languages)
 }
 
 /// Generate audio for a single language
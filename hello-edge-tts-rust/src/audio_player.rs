@@ -1,6 +1,12 @@
-use rodio::{Decoder, OutputStream, Sink};
+use crate::tts_client::{Bookmark, WordBoundary};
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use rodio::buffer::SamplesBuffer;
+use rodio::{Decoder, OutputStream, Sink, Source};
 use std::fs::File;
-use std::io::{BufReader, Cursor};
+use std::io::{BufReader, Cursor, Read, Seek, SeekFrom};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
 
 /// Custom error type for audio operations
 #[derive(Debug, thiserror::Error)]
@@ -13,36 +19,536 @@ pub enum AudioError {
     Playback(String),
     #[error("Audio device error: {0}")]
     Device(String),
+    #[error("Audio processing error: {0}")]
+    Processing(String),
+}
+
+/// Audio format hint for playback, so callers can name the encoding instead
+/// of relying on container sniffing. Raw PCM carries its sample parameters
+/// since there is no header to read them from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Mp3,
+    Wav,
+    Ogg,
+    /// Raw signed 16-bit little-endian PCM
+    Pcm { sample_rate: u32, channels: u16 },
+}
+
+impl AudioFormat {
+    /// Parse a textual format hint (e.g., "mp3", "wav", "ogg")
+    pub fn from_hint(hint: &str) -> Option<Self> {
+        match hint.to_lowercase().as_str() {
+            "mp3" => Some(Self::Mp3),
+            "wav" => Some(Self::Wav),
+            "ogg" | "oga" | "vorbis" => Some(Self::Ogg),
+            _ => None,
+        }
+    }
+}
+
+/// A per-sample processing stage in the playback DSP chain.
+///
+/// Samples are full-scale floats (-1.0 to 1.0) and arrive channel-interleaved;
+/// stages keeping filter state across samples should account for that when
+/// processing multi-channel audio.
+pub trait DspStage: Send + Sync {
+    fn process(&mut self, sample: f32) -> f32;
+}
+
+/// Fixed gain stage
+pub struct GainStage {
+    factor: f32,
+}
+
+impl GainStage {
+    /// Create a gain stage from a decibel value (positive boosts, negative cuts)
+    pub fn new(gain_db: f32) -> Self {
+        Self {
+            factor: 10f32.powf(gain_db / 20.0),
+        }
+    }
+}
+
+impl DspStage for GainStage {
+    fn process(&mut self, sample: f32) -> f32 {
+        sample * self.factor
+    }
+}
+
+/// Simple one-pole low or high shelf EQ stage
+pub struct ShelfStage {
+    high: bool,
+    gain_factor: f32,
+    alpha: f32,
+    lowpass_state: f32,
+}
+
+impl ShelfStage {
+    /// Create a low shelf boosting or cutting below `cutoff_hz`
+    pub fn low(cutoff_hz: f32, gain_db: f32, sample_rate: u32) -> Self {
+        Self::new(false, cutoff_hz, gain_db, sample_rate)
+    }
+
+    /// Create a high shelf boosting or cutting above `cutoff_hz`
+    pub fn high(cutoff_hz: f32, gain_db: f32, sample_rate: u32) -> Self {
+        Self::new(true, cutoff_hz, gain_db, sample_rate)
+    }
+
+    fn new(high: bool, cutoff_hz: f32, gain_db: f32, sample_rate: u32) -> Self {
+        let dt = 1.0 / sample_rate as f32;
+        let rc = 1.0 / (2.0 * std::f32::consts::PI * cutoff_hz);
+        Self {
+            high,
+            gain_factor: 10f32.powf(gain_db / 20.0) - 1.0,
+            alpha: dt / (rc + dt),
+            lowpass_state: 0.0,
+        }
+    }
+}
+
+impl DspStage for ShelfStage {
+    fn process(&mut self, sample: f32) -> f32 {
+        self.lowpass_state += self.alpha * (sample - self.lowpass_state);
+        let band = if self.high {
+            sample - self.lowpass_state
+        } else {
+            self.lowpass_state
+        };
+        sample + self.gain_factor * band
+    }
+}
+
+/// Soft limiter keeping output within a threshold, for noisy environments
+/// where speech is boosted close to clipping
+pub struct LimiterStage {
+    threshold: f32,
+}
+
+impl LimiterStage {
+    /// Create a limiter with a full-scale threshold (0.0 to 1.0)
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold: threshold.clamp(0.01, 1.0),
+        }
+    }
+}
+
+impl DspStage for LimiterStage {
+    fn process(&mut self, sample: f32) -> f32 {
+        self.threshold * (sample / self.threshold).tanh()
+    }
+}
+
+/// Observer for playback lifecycle events, so GUIs and bots can update state
+/// without polling the player. All methods have no-op defaults; implement only
+/// the events you care about.
+pub trait PlaybackObserver {
+    /// Called when a clip has been queued and playback is starting
+    fn on_started(&self) {}
+    /// Called when playback of all queued audio has finished
+    fn on_finished(&self) {}
+    /// Called when playback fails before or during decoding
+    fn on_error(&self, _error: &AudioError) {}
+    /// Called periodically with the current playback position
+    fn on_position(&self, _position: Duration) {}
+    /// Called when playback reaches the start of a word, when word boundary
+    /// metadata was supplied alongside the audio
+    fn on_word(&self, _index: usize, _word: &WordBoundary) {}
+    /// Called when playback reaches a `<bookmark>` position, when bookmark
+    /// metadata was supplied alongside the audio
+    fn on_bookmark(&self, _bookmark: &Bookmark) {}
+}
+
+/// Growable byte buffer shared between a producer (network stream) and a
+/// consumer (audio decoder). The decoder blocks on reads until data arrives,
+/// which lets playback start before the full download has finished.
+struct StreamBuffer {
+    state: Mutex<StreamBufferState>,
+    available: Condvar,
+}
+
+struct StreamBufferState {
+    data: Vec<u8>,
+    finished: bool,
+}
+
+impl StreamBuffer {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(StreamBufferState {
+                data: Vec::new(),
+                finished: false,
+            }),
+            available: Condvar::new(),
+        }
+    }
+
+    fn push(&self, chunk: &[u8]) {
+        let mut state = self.state.lock().unwrap();
+        state.data.extend_from_slice(chunk);
+        self.available.notify_all();
+    }
+
+    fn finish(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.finished = true;
+        self.available.notify_all();
+    }
+}
+
+/// Blocking reader over a [`StreamBuffer`]. Already-received data stays in the
+/// buffer so the decoder can seek backwards during format probing.
+struct StreamBufferReader {
+    buffer: Arc<StreamBuffer>,
+    pos: usize,
+}
+
+impl Read for StreamBufferReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut state = self.buffer.state.lock().unwrap();
+        while self.pos >= state.data.len() && !state.finished {
+            state = self.buffer.available.wait(state).unwrap();
+        }
+        if self.pos >= state.data.len() {
+            return Ok(0); // EOF
+        }
+        let available = &state.data[self.pos..];
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl Seek for StreamBufferReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => offset as i64,
+            SeekFrom::Current(offset) => self.pos as i64 + offset,
+            SeekFrom::End(offset) => {
+                // The total length is only known once the stream is complete.
+                let mut state = self.buffer.state.lock().unwrap();
+                while !state.finished {
+                    state = self.buffer.available.wait(state).unwrap();
+                }
+                state.data.len() as i64 + offset
+            }
+        };
+        if new_pos < 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "seek before start of stream",
+            ));
+        }
+        self.pos = new_pos as usize;
+        Ok(self.pos as u64)
+    }
+}
+
+/// Playback backend: a real audio device, or a no-op sink for headless
+/// environments (servers, CI) that have no sound hardware
+enum Backend {
+    Device {
+        /// The owning player keeps the output stream alive; players created
+        /// from a shared [`AudioMixer`] don't own one.
+        _stream: Option<OutputStream>,
+        sink: Sink,
+    },
+    Null {
+        volume: Mutex<f32>,
+    },
+}
+
+/// Shared audio output hosting several independent [`AudioPlayer`] sinks
+/// that play simultaneously and are mixed together — e.g., a chime on one
+/// player while speech plays on another, each with its own volume.
+pub struct AudioMixer {
+    _stream: OutputStream,
+    stream_handle: rodio::OutputStreamHandle,
+}
+
+impl AudioMixer {
+    /// Open the default audio device for mixing
+    pub fn new() -> Result<Self, AudioError> {
+        let (_stream, stream_handle) = OutputStream::try_default()
+            .map_err(|e| AudioError::Device(format!("Failed to get audio device: {}", e)))?;
+        Ok(Self {
+            _stream,
+            stream_handle,
+        })
+    }
+
+    /// Create an independent player on this mixer's output. The mixer must
+    /// outlive the players created from it.
+    pub fn create_player(&self) -> Result<AudioPlayer, AudioError> {
+        let sink = Sink::try_new(&self.stream_handle)
+            .map_err(|e| AudioError::Device(format!("Failed to create audio sink: {}", e)))?;
+        Ok(AudioPlayer::with_backend(Backend::Device {
+            _stream: None,
+            sink,
+        }))
+    }
 }
 
 /// Audio player for cross-platform audio playback
 pub struct AudioPlayer {
-    _stream: OutputStream,
-    sink: Sink,
+    backend: Backend,
+    normalization_peak: Option<f32>,
+    observer: Option<Arc<dyn PlaybackObserver + Send + Sync>>,
+    position: Arc<Mutex<Duration>>,
+    dsp_stages: Mutex<Vec<Box<dyn DspStage>>>,
+    barge_in: bool,
+    barge_in_fade: Option<Duration>,
 }
 
 impl AudioPlayer {
-    /// Create a new AudioPlayer instance
+    /// Create a new AudioPlayer instance.
+    ///
+    /// If no audio device is available (headless servers, CI) this falls back
+    /// to a no-op null backend so library consumers and `--no-play` CLI runs
+    /// keep working; playback calls then validate and discard the audio.
     pub fn new() -> Result<Self, AudioError> {
+        match Self::open_device() {
+            Ok(backend) => Ok(Self::with_backend(backend)),
+            Err(e) => {
+                tracing::warn!("No audio device available ({}), using null audio backend", e);
+                Ok(Self::null())
+            }
+        }
+    }
+
+    /// Create an AudioPlayer that requires a real audio device, failing if
+    /// none is available instead of falling back to the null backend
+    pub fn try_new_device() -> Result<Self, AudioError> {
+        Ok(Self::with_backend(Self::open_device()?))
+    }
+
+    /// Create an AudioPlayer targeting a specific audio host (e.g., "alsa",
+    /// "jack"), for Linux users who need a particular sound server instead of
+    /// whatever cpal picks by default. Backend names are matched
+    /// case-insensitively against [`AudioPlayer::available_backends`].
+    pub fn new_with_backend(backend_name: &str) -> Result<Self, AudioError> {
+        use rodio::cpal::traits::HostTrait;
+
+        let host_id = rodio::cpal::available_hosts()
+            .into_iter()
+            .find(|id| id.name().eq_ignore_ascii_case(backend_name))
+            .ok_or_else(|| {
+                AudioError::Device(format!(
+                    "Unknown audio backend '{}'. Available: {}",
+                    backend_name,
+                    Self::available_backends().join(", ")
+                ))
+            })?;
+
+        let host = rodio::cpal::host_from_id(host_id)
+            .map_err(|e| AudioError::Device(format!("Failed to initialize audio host: {}", e)))?;
+
+        let device = host.default_output_device().ok_or_else(|| {
+            AudioError::Device(format!("No output device on backend '{}'", backend_name))
+        })?;
+
+        let (_stream, stream_handle) = OutputStream::try_from_device(&device)
+            .map_err(|e| AudioError::Device(format!("Failed to open audio device: {}", e)))?;
+
+        let sink = Sink::try_new(&stream_handle)
+            .map_err(|e| AudioError::Device(format!("Failed to create audio sink: {}", e)))?;
+
+        Ok(Self::with_backend(Backend::Device {
+            _stream: Some(_stream),
+            sink,
+        }))
+    }
+
+    /// List the audio hosts compiled into this build (e.g., ALSA and JACK on
+    /// Linux, WASAPI on Windows, CoreAudio on macOS)
+    pub fn available_backends() -> Vec<String> {
+        rodio::cpal::available_hosts()
+            .into_iter()
+            .map(|id| id.name().to_string())
+            .collect()
+    }
+
+    /// Create a no-op AudioPlayer that decodes and discards audio, for
+    /// headless environments
+    pub fn null() -> Self {
+        Self::with_backend(Backend::Null {
+            volume: Mutex::new(1.0),
+        })
+    }
+
+    /// Check whether this player uses the no-op null backend
+    pub fn is_null(&self) -> bool {
+        matches!(self.backend, Backend::Null { .. })
+    }
+
+    fn open_device() -> Result<Backend, AudioError> {
         let (_stream, stream_handle) = OutputStream::try_default()
             .map_err(|e| AudioError::Device(format!("Failed to get audio device: {}", e)))?;
 
         let sink = Sink::try_new(&stream_handle)
             .map_err(|e| AudioError::Device(format!("Failed to create audio sink: {}", e)))?;
 
-        Ok(Self { _stream, sink })
+        Ok(Backend::Device {
+            _stream: Some(_stream),
+            sink,
+        })
     }
 
-    /// Play audio from a file
-    pub fn play_file(&self, filename: &str) -> Result<(), AudioError> {
+    fn with_backend(backend: Backend) -> Self {
+        Self {
+            backend,
+            normalization_peak: None,
+            observer: None,
+            position: Arc::new(Mutex::new(Duration::ZERO)),
+            dsp_stages: Mutex::new(Vec::new()),
+            barge_in: false,
+            barge_in_fade: None,
+        }
+    }
+
+    /// Enable barge-in: starting a new `play_*` call immediately stops
+    /// whatever is currently playing, optionally ramping the volume down over
+    /// `fade` first — standard behavior for voice-assistant-style apps.
+    pub fn set_barge_in(&mut self, enabled: bool, fade: Option<Duration>) {
+        self.barge_in = enabled;
+        self.barge_in_fade = fade;
+    }
+
+    /// Cut off any current playback before a new utterance when barge-in is
+    /// enabled
+    fn prepare_playback(&self) {
+        if !self.barge_in {
+            return;
+        }
+        let Some(sink) = self.sink() else { return };
+        if sink.empty() {
+            return;
+        }
+
+        if let Some(fade) = self.barge_in_fade {
+            let steps = 8u32;
+            let original = sink.volume();
+            for step in (0..steps).rev() {
+                sink.set_volume(original * step as f32 / steps as f32);
+                std::thread::sleep(fade / steps);
+            }
+            sink.stop();
+            sink.set_volume(original);
+        } else {
+            sink.stop();
+        }
+    }
+
+    /// Add a processing stage to the playback DSP chain. Stages run in
+    /// registration order on `play_file`/`play_audio_data` audio.
+    pub fn add_dsp_stage(&self, stage: Box<dyn DspStage>) {
+        self.dsp_stages.lock().unwrap().push(stage);
+    }
+
+    /// Remove all registered DSP stages
+    pub fn clear_dsp_stages(&self) {
+        self.dsp_stages.lock().unwrap().clear();
+    }
+
+    fn sink(&self) -> Option<&Sink> {
+        match &self.backend {
+            Backend::Device { sink, .. } => Some(sink),
+            Backend::Null { .. } => None,
+        }
+    }
+
+    /// Get the playback position within the currently playing audio, updated
+    /// roughly every 100ms while audio is playing
+    pub fn current_position(&self) -> Duration {
+        *self.position.lock().unwrap()
+    }
+
+    /// Get the duration of an audio file without playing it
+    pub fn duration_of_file(filename: &str) -> Result<Duration, AudioError> {
         let file = File::open(filename)?;
         let source = Decoder::new(BufReader::new(file))
             .map_err(|e| AudioError::Decode(format!("Failed to decode audio file: {}", e)))?;
+        Ok(Self::source_duration(source))
+    }
+
+    /// Get the duration of in-memory audio data without playing it
+    pub fn duration_of_data(audio_data: Vec<u8>) -> Result<Duration, AudioError> {
+        let source = Decoder::new(Cursor::new(audio_data))
+            .map_err(|e| AudioError::Decode(format!("Failed to decode audio data: {}", e)))?;
+        Ok(Self::source_duration(source))
+    }
+
+    /// Compute a source's duration, falling back to counting decoded samples
+    /// for formats (like MP3) where the decoder can't report it up front
+    fn source_duration<R>(source: Decoder<R>) -> Duration
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
+        if let Some(duration) = source.total_duration() {
+            return duration;
+        }
+
+        let channels = source.channels() as u64;
+        let sample_rate = source.sample_rate() as u64;
+        let sample_count = source.count() as u64;
+        if channels == 0 || sample_rate == 0 {
+            return Duration::ZERO;
+        }
+        Duration::from_nanos(sample_count * 1_000_000_000 / (channels * sample_rate))
+    }
+
+    /// Register an observer notified of playback events
+    pub fn set_observer(&mut self, observer: Arc<dyn PlaybackObserver + Send + Sync>) {
+        self.observer = Some(observer);
+    }
 
-        self.sink.append(source);
+    /// Remove the registered playback observer
+    pub fn clear_observer(&mut self) {
+        self.observer = None;
+    }
+
+    /// Enable or disable loudness normalization at playback time.
+    ///
+    /// When set, each clip is analyzed and scaled so its peak matches
+    /// `target_peak` (0.0 to 1.0 of full scale), giving consistent volume
+    /// across voices that differ considerably in level. Pass `None` to play
+    /// audio at its original level.
+    pub fn set_normalization(&mut self, target_peak: Option<f32>) {
+        self.normalization_peak = target_peak.map(|p| p.clamp(0.0, 1.0));
+    }
+
+    /// Play audio from a file
+    pub fn play_file(&self, filename: &str) -> Result<(), AudioError> {
+        self.play_file_with_fade(filename, Duration::ZERO, Duration::ZERO)
+    }
+
+    /// Play audio from a file with fade-in and fade-out ramps, so announcements
+    /// blended into other audio don't start or end abruptly
+    pub fn play_file_with_fade(
+        &self,
+        filename: &str,
+        fade_in: Duration,
+        fade_out: Duration,
+    ) -> Result<(), AudioError> {
+        self.prepare_playback();
+        let file = File::open(filename).map_err(|e| self.notify_error(AudioError::Io(e)))?;
+        let source = Decoder::new(BufReader::new(file)).map_err(|e| {
+            self.notify_error(AudioError::Decode(format!(
+                "Failed to decode audio file: {}",
+                e
+            )))
+        })?;
+
+        self.notify_started();
+        self.append_with_fade(source, fade_in, fade_out);
 
         // Wait for playback to complete
-        self.sink.sleep_until_end();
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
 
         Ok(())
     }
@@ -53,48 +559,468 @@ impl AudioPlayer {
         audio_data: Vec<u8>,
         format_hint: Option<&str>,
     ) -> Result<(), AudioError> {
-        let _format_hint = format_hint.unwrap_or("mp3"); // Store for potential future use
+        self.play_audio_data_with_fade(audio_data, format_hint, Duration::ZERO, Duration::ZERO)
+    }
 
+    /// Play audio from raw audio data with fade-in and fade-out ramps
+    pub fn play_audio_data_with_fade(
+        &self,
+        audio_data: Vec<u8>,
+        format_hint: Option<&str>,
+        fade_in: Duration,
+        fade_out: Duration,
+    ) -> Result<(), AudioError> {
+        self.prepare_playback();
         let cursor = Cursor::new(audio_data);
-        let source = Decoder::new(cursor)
-            .map_err(|e| AudioError::Decode(format!("Failed to decode audio data: {}", e)))?;
+        let source = match format_hint.and_then(AudioFormat::from_hint) {
+            Some(AudioFormat::Mp3) => Decoder::new_mp3(cursor),
+            Some(AudioFormat::Wav) => Decoder::new_wav(cursor),
+            Some(AudioFormat::Ogg) => Decoder::new_vorbis(cursor),
+            _ => Decoder::new(cursor),
+        }
+        .map_err(|e| {
+            self.notify_error(AudioError::Decode(format!(
+                "Failed to decode audio data: {}",
+                e
+            )))
+        })?;
 
-        self.sink.append(source);
+        self.notify_started();
+        self.append_with_fade(source, fade_in, fade_out);
 
         // Wait for playback to complete
-        self.sink.sleep_until_end();
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
 
         Ok(())
     }
 
+    /// Queue audio data for playback without waiting for it to finish, so
+    /// several players on a shared [`AudioMixer`] can play at the same time
+    pub fn queue_audio_data(
+        &self,
+        audio_data: Vec<u8>,
+        format_hint: Option<&str>,
+    ) -> Result<(), AudioError> {
+        self.prepare_playback();
+        let cursor = Cursor::new(audio_data);
+        let source = match format_hint.and_then(AudioFormat::from_hint) {
+            Some(AudioFormat::Mp3) => Decoder::new_mp3(cursor),
+            Some(AudioFormat::Wav) => Decoder::new_wav(cursor),
+            Some(AudioFormat::Ogg) => Decoder::new_vorbis(cursor),
+            _ => Decoder::new(cursor),
+        }
+        .map_err(|e| {
+            self.notify_error(AudioError::Decode(format!(
+                "Failed to decode audio data: {}",
+                e
+            )))
+        })?;
+
+        self.notify_started();
+        self.append_with_fade(source, Duration::ZERO, Duration::ZERO);
+        Ok(())
+    }
+
+    /// Block until all queued audio has finished playing
+    pub fn wait_until_end(&self) {
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
+    }
+
+    /// Play audio data while emitting `on_word` observer events as playback
+    /// crosses each word boundary, enabling karaoke-style highlighting.
+    /// Boundaries must be sorted by offset.
+    pub fn play_audio_data_with_boundaries(
+        &self,
+        audio_data: Vec<u8>,
+        format_hint: Option<&str>,
+        boundaries: &[WordBoundary],
+    ) -> Result<(), AudioError> {
+        self.play_audio_data_with_events(audio_data, format_hint, boundaries, &[])
+    }
+
+    /// Play audio data while emitting `on_word` and `on_bookmark` observer
+    /// events as playback crosses each boundary or bookmark position. Both
+    /// slices must be sorted by offset.
+    pub fn play_audio_data_with_events(
+        &self,
+        audio_data: Vec<u8>,
+        format_hint: Option<&str>,
+        boundaries: &[WordBoundary],
+        bookmarks: &[Bookmark],
+    ) -> Result<(), AudioError> {
+        self.prepare_playback();
+        let cursor = Cursor::new(audio_data);
+        let source = match format_hint.and_then(AudioFormat::from_hint) {
+            Some(AudioFormat::Mp3) => Decoder::new_mp3(cursor),
+            Some(AudioFormat::Wav) => Decoder::new_wav(cursor),
+            Some(AudioFormat::Ogg) => Decoder::new_vorbis(cursor),
+            _ => Decoder::new(cursor),
+        }
+        .map_err(|e| {
+            self.notify_error(AudioError::Decode(format!(
+                "Failed to decode audio data: {}",
+                e
+            )))
+        })?;
+
+        self.notify_started();
+
+        let observer = self.observer.clone();
+        let position = Arc::clone(&self.position);
+        let boundaries = boundaries.to_vec();
+        let bookmarks = bookmarks.to_vec();
+        let mut next_word = 0usize;
+        let mut next_bookmark = 0usize;
+        let period = Duration::from_millis(50);
+        let wrapped = source.periodic_access(period, move |_| {
+            let current = {
+                let mut position = position.lock().unwrap();
+                *position += period;
+                *position
+            };
+            if let Some(observer) = &observer {
+                observer.on_position(current);
+                while next_word < boundaries.len() && boundaries[next_word].offset <= current {
+                    observer.on_word(next_word, &boundaries[next_word]);
+                    next_word += 1;
+                }
+                while next_bookmark < bookmarks.len()
+                    && bookmarks[next_bookmark].offset <= current
+                {
+                    observer.on_bookmark(&bookmarks[next_bookmark]);
+                    next_bookmark += 1;
+                }
+            }
+        });
+
+        if let Some(sink) = self.sink() {
+            sink.append(wrapped);
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
+
+        Ok(())
+    }
+
+    /// Play audio data of a known format, including raw PCM which carries no
+    /// header for the decoder to sniff
+    pub fn play_audio_data_with_format(
+        &self,
+        audio_data: Vec<u8>,
+        format: AudioFormat,
+    ) -> Result<(), AudioError> {
+        self.prepare_playback();
+        match format {
+            AudioFormat::Pcm {
+                sample_rate,
+                channels,
+            } => {
+                let samples = pcm_bytes_to_samples(&audio_data);
+                self.notify_started();
+                self.append_source(SamplesBuffer::new(channels, sample_rate, samples));
+            }
+            AudioFormat::Mp3 | AudioFormat::Wav | AudioFormat::Ogg => {
+                let cursor = Cursor::new(audio_data);
+                let source = match format {
+                    AudioFormat::Mp3 => Decoder::new_mp3(cursor),
+                    AudioFormat::Wav => Decoder::new_wav(cursor),
+                    _ => Decoder::new_vorbis(cursor),
+                }
+                .map_err(|e| {
+                    self.notify_error(AudioError::Decode(format!(
+                        "Failed to decode {:?} audio data: {}",
+                        format, e
+                    )))
+                })?;
+                self.notify_started();
+                self.append_source(source);
+            }
+        }
+
+        // Wait for playback to complete
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
+
+        Ok(())
+    }
+
+    /// Play a list of audio segments back to back without gaps, as one
+    /// continuous narration. All segments are decoded and queued on the sink
+    /// up front so segment boundaries introduce no pause or click.
+    pub fn play_segments(&self, segments: &[Vec<u8>]) -> Result<(), AudioError> {
+        self.prepare_playback();
+        for (i, segment) in segments.iter().enumerate() {
+            let cursor = Cursor::new(segment.clone());
+            let source = Decoder::new(cursor).map_err(|e| {
+                self.notify_error(AudioError::Decode(format!(
+                    "Failed to decode segment {}: {}",
+                    i + 1,
+                    e
+                )))
+            })?;
+            self.append_source(source);
+        }
+
+        self.notify_started();
+
+        // Wait for playback to complete
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
+
+        Ok(())
+    }
+
+    /// Append a decoded source to the sink, applying normalization and fade
+    /// ramps if requested. Both require analyzing the whole clip, so sources
+    /// needing processing are decoded into memory first.
+    fn append_with_fade<R>(&self, source: Decoder<R>, fade_in: Duration, fade_out: Duration)
+    where
+        R: Read + Seek + Send + Sync + 'static,
+    {
+        let mut dsp_stages = self.dsp_stages.lock().unwrap();
+        if fade_in.is_zero()
+            && fade_out.is_zero()
+            && self.normalization_peak.is_none()
+            && dsp_stages.is_empty()
+        {
+            self.append_source(source);
+            return;
+        }
+
+        let channels = source.channels();
+        let sample_rate = source.sample_rate();
+        let mut samples: Vec<i16> = source.collect();
+        apply_dsp_chain(&mut samples, &mut dsp_stages);
+        if let Some(target_peak) = self.normalization_peak {
+            apply_peak_normalization(&mut samples, target_peak);
+        }
+        apply_fades(&mut samples, channels, sample_rate, fade_in, fade_out);
+
+        self.append_source(SamplesBuffer::new(channels, sample_rate, samples));
+    }
+
+    /// Append a source to the sink, wrapping it with periodic position
+    /// reporting when an observer is registered
+    fn append_source<S>(&self, source: S)
+    where
+        S: Source<Item = i16> + Send + 'static,
+    {
+        let observer = self.observer.clone();
+        let position = Arc::clone(&self.position);
+        let period = Duration::from_millis(100);
+        let wrapped = source.periodic_access(period, move |_| {
+            let current = {
+                let mut position = position.lock().unwrap();
+                *position += period;
+                *position
+            };
+            if let Some(observer) = &observer {
+                observer.on_position(current);
+            }
+        });
+        if let Some(sink) = self.sink() {
+            sink.append(wrapped);
+        }
+    }
+
+    /// Reset the shared position counter at the start of a playback request
+    fn reset_position(&self) {
+        *self.position.lock().unwrap() = Duration::ZERO;
+    }
+
+    fn notify_started(&self) {
+        self.reset_position();
+        if let Some(observer) = &self.observer {
+            observer.on_started();
+        }
+    }
+
+    fn notify_finished(&self) {
+        if let Some(observer) = &self.observer {
+            observer.on_finished();
+        }
+    }
+
+    fn notify_error(&self, error: AudioError) -> AudioError {
+        if let Some(observer) = &self.observer {
+            observer.on_error(&error);
+        }
+        error
+    }
+
+    /// Play audio from a stream of chunks, starting playback as soon as the
+    /// decoder has enough data instead of waiting for the full download.
+    ///
+    /// Combined with streaming synthesis this gets the first words audible
+    /// while the rest of the audio is still being generated.
+    pub async fn play_stream<S>(&self, stream: S) -> Result<(), AudioError>
+    where
+        S: Stream<Item = Bytes> + Send + 'static,
+    {
+        self.prepare_playback();
+        let buffer = Arc::new(StreamBuffer::new());
+
+        let writer = Arc::clone(&buffer);
+        let feeder = tokio::spawn(async move {
+            futures_util::pin_mut!(stream);
+            while let Some(chunk) = stream.next().await {
+                writer.push(&chunk);
+            }
+            writer.finish();
+        });
+
+        // Creating the decoder blocks until enough data has arrived to probe
+        // the format, so it must run off the async executor.
+        let reader = StreamBufferReader { buffer, pos: 0 };
+        let decoder = tokio::task::spawn_blocking(move || Decoder::new(reader))
+            .await
+            .map_err(|e| self.notify_error(AudioError::Playback(format!("Decoder task failed: {}", e))))?
+            .map_err(|e| {
+                self.notify_error(AudioError::Decode(format!(
+                    "Failed to decode audio stream: {}",
+                    e
+                )))
+            })?;
+
+        self.notify_started();
+        self.append_source(decoder);
+        if let Some(sink) = self.sink() {
+            sink.sleep_until_end();
+        }
+        self.notify_finished();
+
+        let _ = feeder.await;
+        Ok(())
+    }
+
     /// Stop current playback
     pub fn stop(&self) {
-        self.sink.stop();
+        if let Some(sink) = self.sink() {
+            sink.stop();
+        }
     }
 
     /// Pause current playback
     pub fn pause(&self) {
-        self.sink.pause();
+        if let Some(sink) = self.sink() {
+            sink.pause();
+        }
     }
 
     /// Resume paused playback
     pub fn resume(&self) {
-        self.sink.play();
+        if let Some(sink) = self.sink() {
+            sink.play();
+        }
     }
 
     /// Check if audio is currently playing
     pub fn is_playing(&self) -> bool {
-        !self.sink.empty()
+        match self.sink() {
+            Some(sink) => !sink.empty(),
+            None => false,
+        }
     }
 
     /// Set playback volume (0.0 to 1.0)
     pub fn set_volume(&self, volume: f32) {
-        self.sink.set_volume(volume.clamp(0.0, 1.0));
+        match &self.backend {
+            Backend::Device { sink, .. } => sink.set_volume(volume.clamp(0.0, 1.0)),
+            Backend::Null { volume: stored } => *stored.lock().unwrap() = volume.clamp(0.0, 1.0),
+        }
     }
 
     /// Get current playback volume
     pub fn volume(&self) -> f32 {
-        self.sink.volume()
+        match &self.backend {
+            Backend::Device { sink, .. } => sink.volume(),
+            Backend::Null { volume } => *volume.lock().unwrap(),
+        }
+    }
+
+    /// Set playback speed (1.0 = normal; also shifts pitch, as with rodio)
+    pub fn set_speed(&self, speed: f32) {
+        if let Some(sink) = self.sink() {
+            sink.set_speed(speed.max(0.1));
+        }
+    }
+
+    /// Get current playback speed
+    pub fn speed(&self) -> f32 {
+        match self.sink() {
+            Some(sink) => sink.speed(),
+            None => 1.0,
+        }
+    }
+}
+
+/// Convert raw little-endian 16-bit PCM bytes into samples, ignoring a
+/// trailing odd byte if present
+fn pcm_bytes_to_samples(data: &[u8]) -> Vec<i16> {
+    data.chunks_exact(2)
+        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+        .collect()
+}
+
+/// Run PCM samples through the registered DSP stages in order
+fn apply_dsp_chain(samples: &mut [i16], stages: &mut [Box<dyn DspStage>]) {
+    if stages.is_empty() {
+        return;
+    }
+
+    for sample in samples.iter_mut() {
+        let mut value = *sample as f32 / i16::MAX as f32;
+        for stage in stages.iter_mut() {
+            value = stage.process(value);
+        }
+        *sample = (value * i16::MAX as f32).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
+/// Scale PCM samples so the loudest peak matches `target_peak` of full scale
+fn apply_peak_normalization(samples: &mut [i16], target_peak: f32) {
+    let peak = samples.iter().map(|s| s.unsigned_abs()).max().unwrap_or(0);
+    if peak == 0 {
+        return; // pure silence, nothing to normalize
+    }
+
+    let factor = target_peak * i16::MAX as f32 / peak as f32;
+    for sample in samples.iter_mut() {
+        *sample = (*sample as f32 * factor).clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+    }
+}
+
+/// Apply linear fade-in and fade-out ramps to interleaved PCM samples
+fn apply_fades(
+    samples: &mut [i16],
+    channels: u16,
+    sample_rate: u32,
+    fade_in: Duration,
+    fade_out: Duration,
+) {
+    let frames_per_sec = sample_rate as f32 * channels as f32;
+
+    let fade_in_len = ((fade_in.as_secs_f32() * frames_per_sec) as usize).min(samples.len());
+    for (i, sample) in samples[..fade_in_len].iter_mut().enumerate() {
+        let factor = i as f32 / fade_in_len as f32;
+        *sample = (*sample as f32 * factor) as i16;
+    }
+
+    let fade_out_len = ((fade_out.as_secs_f32() * frames_per_sec) as usize).min(samples.len());
+    let start = samples.len() - fade_out_len;
+    for (i, sample) in samples[start..].iter_mut().enumerate() {
+        let factor = (fade_out_len - i) as f32 / fade_out_len as f32;
+        *sample = (*sample as f32 * factor) as i16;
     }
 }
 
@@ -128,6 +1054,146 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_gain_stage() {
+        let mut stage = GainStage::new(6.0);
+        let boosted = stage.process(0.25);
+        assert!((boosted - 0.499).abs() < 0.01); // +6dB roughly doubles
+    }
+
+    #[test]
+    fn test_limiter_stage_bounds_output() {
+        let mut stage = LimiterStage::new(0.5);
+        assert!(stage.process(10.0) <= 0.5);
+        assert!(stage.process(-10.0) >= -0.5);
+        // Quiet samples pass nearly untouched
+        assert!((stage.process(0.05) - 0.05).abs() < 0.005);
+    }
+
+    #[test]
+    fn test_apply_dsp_chain_runs_stages_in_order() {
+        let mut samples = vec![8192i16; 4];
+        let mut stages: Vec<Box<dyn DspStage>> =
+            vec![Box::new(GainStage::new(6.0)), Box::new(LimiterStage::new(0.3))];
+        apply_dsp_chain(&mut samples, &mut stages);
+
+        let limit = (0.3 * i16::MAX as f32) as i16;
+        assert!(samples.iter().all(|&s| s <= limit));
+    }
+
+    #[test]
+    fn test_available_backends_nonempty() {
+        assert!(!AudioPlayer::available_backends().is_empty());
+    }
+
+    #[test]
+    fn test_new_with_unknown_backend() {
+        let result = AudioPlayer::new_with_backend("no-such-backend");
+        assert!(matches!(result, Err(AudioError::Device(_))));
+    }
+
+    #[test]
+    fn test_null_player_headless_playback() {
+        let player = AudioPlayer::null();
+        assert!(player.is_null());
+        assert!(!player.is_playing());
+
+        player.set_volume(0.3);
+        assert_eq!(player.volume(), 0.3);
+
+        // Raw PCM is decoded and discarded without touching a device
+        let result = player.play_audio_data_with_format(
+            vec![0u8; 64],
+            AudioFormat::Pcm {
+                sample_rate: 8000,
+                channels: 1,
+            },
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_audio_format_from_hint() {
+        assert_eq!(AudioFormat::from_hint("mp3"), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::from_hint("WAV"), Some(AudioFormat::Wav));
+        assert_eq!(AudioFormat::from_hint("ogg"), Some(AudioFormat::Ogg));
+        assert_eq!(AudioFormat::from_hint("flac"), None);
+    }
+
+    #[test]
+    fn test_pcm_bytes_to_samples() {
+        let samples = pcm_bytes_to_samples(&[0x00, 0x01, 0xFF, 0x7F, 0x42]);
+        assert_eq!(samples, vec![256, i16::MAX]);
+    }
+
+    #[test]
+    fn test_apply_peak_normalization() {
+        let mut samples = vec![8192i16, -16384, 4096];
+        apply_peak_normalization(&mut samples, 1.0);
+
+        // Loudest sample scaled to full scale, others proportionally
+        assert_eq!(samples[1], i16::MIN + 1);
+        assert!((samples[0] - i16::MAX / 2).abs() <= 1);
+
+        // Silence is left untouched
+        let mut silence = vec![0i16; 4];
+        apply_peak_normalization(&mut silence, 1.0);
+        assert_eq!(silence, vec![0i16; 4]);
+    }
+
+    #[test]
+    fn test_apply_fades() {
+        // 1 channel at 4 Hz: a 1s fade covers 4 samples on each end
+        let mut samples = vec![1000i16; 8];
+        apply_fades(
+            &mut samples,
+            1,
+            4,
+            Duration::from_secs(1),
+            Duration::from_secs(1),
+        );
+
+        assert_eq!(samples[0], 0); // fade-in starts silent
+        assert!(samples[1] < samples[2]); // ramps up
+        assert!(samples[5] > samples[6]); // ramps down
+        assert!(samples[7] < 1000); // fade-out ends quiet
+    }
+
+    #[test]
+    fn test_apply_fades_zero_duration_is_noop() {
+        let mut samples = vec![1000i16; 4];
+        apply_fades(&mut samples, 1, 4, Duration::ZERO, Duration::ZERO);
+        assert_eq!(samples, vec![1000i16; 4]);
+    }
+
+    #[test]
+    fn test_stream_buffer_reader_blocks_until_data() {
+        let buffer = Arc::new(StreamBuffer::new());
+        let mut reader = StreamBufferReader {
+            buffer: Arc::clone(&buffer),
+            pos: 0,
+        };
+
+        let writer = Arc::clone(&buffer);
+        let handle = std::thread::spawn(move || {
+            writer.push(b"hello");
+            writer.push(b" world");
+            writer.finish();
+        });
+
+        let mut out = Vec::new();
+        reader.read_to_end(&mut out).unwrap();
+        assert_eq!(out, b"hello world");
+
+        // Seeking back into already-received data works.
+        reader.seek(SeekFrom::Start(6)).unwrap();
+        let mut buf = [0u8; 5];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"world");
+
+        handle.join().unwrap();
+    }
+
     #[test]
     fn test_playback_controls() {
         if let Ok(player) = AudioPlayer::new() {
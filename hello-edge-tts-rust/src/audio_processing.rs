@@ -0,0 +1,1144 @@
+//! Audio post-processing utilities for synthesized speech
+//!
+//! This module operates on decoded PCM so the results are correct regardless
+//! of the container format the service returned. Processed audio is written
+//! back out as WAV, which every downstream tool accepts.
+
+use crate::audio_player::{AudioError, AudioFormat};
+use crate::tts_client::AudioTags;
+use rodio::{Decoder, Source};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Decoded PCM audio with its sample parameters
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PcmAudio {
+    /// Interleaved signed 16-bit samples
+    pub samples: Vec<i16>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+impl PcmAudio {
+    /// Create PCM audio from raw samples
+    pub fn new(samples: Vec<i16>, sample_rate: u32, channels: u16) -> Self {
+        Self {
+            samples,
+            sample_rate,
+            channels,
+        }
+    }
+
+    /// Decode MP3/WAV/OGG bytes into PCM
+    pub fn decode(audio_data: &[u8]) -> Result<Self, AudioError> {
+        let source = Decoder::new(Cursor::new(audio_data.to_vec()))
+            .map_err(|e| AudioError::Decode(format!("Failed to decode audio data: {}", e)))?;
+
+        let sample_rate = source.sample_rate();
+        let channels = source.channels();
+        Ok(Self {
+            samples: source.collect(),
+            sample_rate,
+            channels,
+        })
+    }
+
+    /// Decode an audio file into PCM
+    pub fn decode_file(path: &std::path::Path) -> Result<Self, AudioError> {
+        let data = std::fs::read(path)?;
+        Self::decode(&data)
+    }
+
+    /// Duration of the audio
+    pub fn duration(&self) -> std::time::Duration {
+        if self.sample_rate == 0 || self.channels == 0 {
+            return std::time::Duration::ZERO;
+        }
+        let frames = self.samples.len() as u64 / self.channels as u64;
+        std::time::Duration::from_nanos(frames * 1_000_000_000 / self.sample_rate as u64)
+    }
+
+    /// Encode as WAV bytes
+    pub fn to_wav_bytes(&self) -> Result<Vec<u8>, AudioError> {
+        let spec = hound::WavSpec {
+            channels: self.channels,
+            sample_rate: self.sample_rate,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut writer = hound::WavWriter::new(&mut cursor, spec)
+                .map_err(|e| AudioError::Decode(format!("Failed to create WAV writer: {}", e)))?;
+            for &sample in &self.samples {
+                writer
+                    .write_sample(sample)
+                    .map_err(|e| AudioError::Decode(format!("Failed to write WAV data: {}", e)))?;
+            }
+            writer
+                .finalize()
+                .map_err(|e| AudioError::Decode(format!("Failed to finalize WAV data: {}", e)))?;
+        }
+        Ok(cursor.into_inner())
+    }
+}
+
+/// Decode audio data (typically the service's MP3 output) to PCM WAV bytes,
+/// for telephony systems and game engines that require WAV input
+pub fn to_wav(audio_data: &[u8]) -> Result<Vec<u8>, AudioError> {
+    PcmAudio::decode(audio_data)?.to_wav_bytes()
+}
+
+/// Transcode audio between the supported formats, so the crate's
+/// `output_format` setting works regardless of what the service returned.
+///
+/// WAV and raw PCM targets are handled natively; MP3 and OGG/Opus encoding is
+/// delegated to `ffmpeg` on the PATH.
+pub async fn transcode(
+    input: &[u8],
+    from: AudioFormat,
+    to: AudioFormat,
+) -> Result<Vec<u8>, AudioError> {
+    if from == to {
+        return Ok(input.to_vec());
+    }
+
+    // Normalize the input to PCM first
+    let pcm = match from {
+        AudioFormat::Pcm {
+            sample_rate,
+            channels,
+        } => PcmAudio::new(
+            input
+                .chunks_exact(2)
+                .map(|pair| i16::from_le_bytes([pair[0], pair[1]]))
+                .collect(),
+            sample_rate,
+            channels,
+        ),
+        _ => PcmAudio::decode(input)?,
+    };
+
+    match to {
+        AudioFormat::Wav => pcm.to_wav_bytes(),
+        AudioFormat::Pcm {
+            sample_rate,
+            channels,
+        } => {
+            if sample_rate != pcm.sample_rate || channels != pcm.channels {
+                return Err(AudioError::Processing(format!(
+                    "PCM target wants {} Hz / {} channel(s) but source is {} Hz / {} channel(s); \
+                     convert the sample format first",
+                    sample_rate, channels, pcm.sample_rate, pcm.channels
+                )));
+            }
+            Ok(pcm
+                .samples
+                .iter()
+                .flat_map(|s| s.to_le_bytes())
+                .collect())
+        }
+        AudioFormat::Mp3 | AudioFormat::Ogg => encode_via_ffmpeg(&pcm, to).await,
+    }
+}
+
+/// Encode PCM to a compressed format by shelling out to ffmpeg
+async fn encode_via_ffmpeg(pcm: &PcmAudio, to: AudioFormat) -> Result<Vec<u8>, AudioError> {
+    use tokio::process::Command;
+
+    let extension = match to {
+        AudioFormat::Mp3 => "mp3",
+        AudioFormat::Ogg => "ogg",
+        _ => unreachable!("only compressed targets are encoded via ffmpeg"),
+    };
+
+    let work_dir = std::env::temp_dir();
+    let id = uuid::Uuid::new_v4();
+    let input_path = work_dir.join(format!("tts_transcode_{}.wav", id));
+    let output_path = work_dir.join(format!("tts_transcode_{}.{}", id, extension));
+
+    tokio::fs::write(&input_path, pcm.to_wav_bytes()?).await?;
+
+    let result = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-i",
+            input_path.to_str().unwrap(),
+            output_path.to_str().unwrap(),
+        ])
+        .output()
+        .await;
+
+    let _ = tokio::fs::remove_file(&input_path).await;
+
+    let output_result = result
+        .map_err(|e| AudioError::Processing(format!("Failed to execute ffmpeg: {}", e)))?;
+    if !output_result.status.success() {
+        let _ = tokio::fs::remove_file(&output_path).await;
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        return Err(AudioError::Processing(format!(
+            "ffmpeg transcode to {} failed: {}",
+            extension, stderr
+        )));
+    }
+
+    let encoded = tokio::fs::read(&output_path).await?;
+    let _ = tokio::fs::remove_file(&output_path).await;
+    Ok(encoded)
+}
+
+/// Join audio files produced by chunked or batch synthesis into one valid
+/// output file (WAV bytes). Segments must share sample rate and channel count.
+pub fn concat(paths: &[PathBuf]) -> Result<Vec<u8>, AudioError> {
+    let segments: Result<Vec<_>, _> = paths.iter().map(|p| PcmAudio::decode_file(p)).collect();
+    concat_pcm(&segments?)?.to_wav_bytes()
+}
+
+/// In-memory variant of [`concat`] joining already-loaded segments
+pub fn concat_data(segments: &[Vec<u8>]) -> Result<Vec<u8>, AudioError> {
+    let decoded: Result<Vec<_>, _> = segments.iter().map(|s| PcmAudio::decode(s)).collect();
+    concat_pcm(&decoded?)?.to_wav_bytes()
+}
+
+/// Measure the integrated loudness of PCM audio in LUFS (EBU R128)
+pub fn measure_loudness(audio: &PcmAudio) -> Result<f64, AudioError> {
+    let mut meter = ebur128::EbuR128::new(
+        audio.channels as u32,
+        audio.sample_rate,
+        ebur128::Mode::I,
+    )
+    .map_err(|e| AudioError::Decode(format!("Failed to create loudness meter: {}", e)))?;
+
+    meter
+        .add_frames_i16(&audio.samples)
+        .map_err(|e| AudioError::Decode(format!("Failed to measure loudness: {}", e)))?;
+
+    meter
+        .loudness_global()
+        .map_err(|e| AudioError::Decode(format!("Failed to compute loudness: {}", e)))
+}
+
+/// Normalize audio to a target integrated loudness (e.g., -16.0 LUFS for
+/// podcasts), so output meets platform loudness requirements without
+/// round-tripping through ffmpeg. Silence is returned unchanged.
+pub fn normalize_loudness(audio: &PcmAudio, target_lufs: f64) -> Result<PcmAudio, AudioError> {
+    let measured = measure_loudness(audio)?;
+    if !measured.is_finite() {
+        return Ok(audio.clone()); // silence or too short to measure
+    }
+
+    let factor = 10f64.powf((target_lufs - measured) / 20.0) as f32;
+    let samples = audio
+        .samples
+        .iter()
+        .map(|&s| (s as f32 * factor).clamp(i16::MIN as f32, i16::MAX as f32) as i16)
+        .collect();
+
+    Ok(PcmAudio::new(samples, audio.sample_rate, audio.channels))
+}
+
+/// Decode audio data, normalize it to `target_lufs`, and re-encode as WAV
+pub fn normalize_loudness_data(audio_data: &[u8], target_lufs: f64) -> Result<Vec<u8>, AudioError> {
+    normalize_loudness(&PcmAudio::decode(audio_data)?, target_lufs)?.to_wav_bytes()
+}
+
+/// Level statistics for generated audio, so automated pipelines can reject
+/// or re-normalize bad output
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioAnalysis {
+    /// Highest absolute sample value, 0.0 to 1.0 of full scale
+    pub peak: f32,
+    /// Root-mean-square level, 0.0 to 1.0 of full scale
+    pub rms: f32,
+    /// Number of samples at or beyond full scale
+    pub clipped_samples: usize,
+    /// Fraction of samples that are clipped
+    pub clipping_ratio: f32,
+}
+
+/// Compute peak, RMS, and clipping statistics for PCM audio
+pub fn analyze(audio: &PcmAudio) -> AudioAnalysis {
+    if audio.samples.is_empty() {
+        return AudioAnalysis {
+            peak: 0.0,
+            rms: 0.0,
+            clipped_samples: 0,
+            clipping_ratio: 0.0,
+        };
+    }
+
+    let mut peak = 0u16;
+    let mut sum_of_squares = 0f64;
+    let mut clipped_samples = 0usize;
+    for &sample in &audio.samples {
+        let abs = sample.unsigned_abs();
+        peak = peak.max(abs);
+        sum_of_squares += (sample as f64) * (sample as f64);
+        if abs >= i16::MAX as u16 {
+            clipped_samples += 1;
+        }
+    }
+
+    let full_scale = i16::MAX as f32;
+    AudioAnalysis {
+        peak: peak as f32 / full_scale,
+        rms: ((sum_of_squares / audio.samples.len() as f64).sqrt() as f32) / full_scale,
+        clipped_samples,
+        clipping_ratio: clipped_samples as f32 / audio.samples.len() as f32,
+    }
+}
+
+/// Decode audio data and compute its level statistics, see [`analyze`]
+pub fn analyze_data(audio_data: &[u8]) -> Result<AudioAnalysis, AudioError> {
+    Ok(analyze(&PcmAudio::decode(audio_data)?))
+}
+
+/// Compute a stable content fingerprint over the decoded PCM samples.
+///
+/// Because the hash covers samples rather than container bytes, re-encoding
+/// the same audio losslessly (e.g., WAV to raw PCM) keeps the fingerprint
+/// stable, which lets cache layers detect duplicates and verify integrity
+/// across format changes.
+pub fn fingerprint(audio: &PcmAudio) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(audio.sample_rate.to_le_bytes());
+    hasher.update(audio.channels.to_le_bytes());
+    for &sample in &audio.samples {
+        hasher.update(sample.to_le_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Decode audio data and fingerprint its PCM content, see [`fingerprint`]
+pub fn fingerprint_data(audio_data: &[u8]) -> Result<String, AudioError> {
+    Ok(fingerprint(&PcmAudio::decode(audio_data)?))
+}
+
+/// Min/max amplitude of one waveform bucket, normalized to -1.0..1.0
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PeakPair {
+    pub min: f32,
+    pub max: f32,
+}
+
+/// Reduce audio to `buckets` min/max amplitude pairs suitable for rendering
+/// waveform previews in web or GUI front-ends
+pub fn compute_peaks(audio: &PcmAudio, buckets: usize) -> Vec<PeakPair> {
+    let frames = audio.samples.len() / audio.channels.max(1) as usize;
+    if buckets == 0 || frames == 0 {
+        return Vec::new();
+    }
+
+    let full_scale = i16::MAX as f32;
+    let mut peaks = Vec::with_capacity(buckets);
+    for bucket in 0..buckets {
+        let start = bucket * frames / buckets;
+        let end = ((bucket + 1) * frames / buckets).max(start + 1).min(frames);
+        let samples =
+            &audio.samples[start * audio.channels as usize..end * audio.channels as usize];
+
+        let mut min = i16::MAX;
+        let mut max = i16::MIN;
+        for &sample in samples {
+            min = min.min(sample);
+            max = max.max(sample);
+        }
+        peaks.push(PeakPair {
+            min: min as f32 / full_scale,
+            max: max as f32 / full_scale,
+        });
+    }
+    peaks
+}
+
+/// Convert audio to a different sample rate and/or channel count (e.g., 8kHz
+/// mono for telephony or 48kHz stereo for video), so downstream systems with
+/// strict audio requirements can consume the output directly.
+///
+/// Resampling uses linear interpolation, which is plenty for speech; channel
+/// conversion averages down to mono and duplicates up from it.
+pub fn convert_format(audio: &PcmAudio, sample_rate: u32, channels: u16) -> PcmAudio {
+    let converted_channels = convert_channels(audio, channels);
+    resample(&converted_channels, sample_rate)
+}
+
+/// Change the channel count, averaging down or duplicating up
+fn convert_channels(audio: &PcmAudio, target: u16) -> PcmAudio {
+    let source = audio.channels.max(1) as usize;
+    let target_usize = target.max(1) as usize;
+    if source == target_usize {
+        return audio.clone();
+    }
+
+    let mut samples = Vec::with_capacity(audio.samples.len() / source * target_usize);
+    for frame in audio.samples.chunks_exact(source) {
+        if target_usize == 1 {
+            let sum: i32 = frame.iter().map(|&s| s as i32).sum();
+            samples.push((sum / source as i32) as i16);
+        } else {
+            // Map existing channels across and fill the rest from the first
+            for channel in 0..target_usize {
+                samples.push(frame[channel.min(source - 1)]);
+            }
+        }
+    }
+
+    PcmAudio::new(samples, audio.sample_rate, target)
+}
+
+/// Change the sample rate using linear interpolation
+fn resample(audio: &PcmAudio, target_rate: u32) -> PcmAudio {
+    if audio.sample_rate == target_rate || audio.sample_rate == 0 {
+        return audio.clone();
+    }
+
+    let channels = audio.channels.max(1) as usize;
+    let source_frames = audio.samples.len() / channels;
+    if source_frames == 0 {
+        return PcmAudio::new(Vec::new(), target_rate, audio.channels);
+    }
+
+    let ratio = audio.sample_rate as f64 / target_rate as f64;
+    let target_frames = (source_frames as f64 / ratio).round() as usize;
+
+    let mut samples = Vec::with_capacity(target_frames * channels);
+    for frame in 0..target_frames {
+        let source_pos = frame as f64 * ratio;
+        let left = source_pos.floor() as usize;
+        let right = (left + 1).min(source_frames - 1);
+        let fraction = source_pos - left as f64;
+
+        for channel in 0..channels {
+            let a = audio.samples[left * channels + channel] as f64;
+            let b = audio.samples[right * channels + channel] as f64;
+            samples.push((a + (b - a) * fraction).round() as i16);
+        }
+    }
+
+    PcmAudio::new(samples, target_rate, audio.channels)
+}
+
+/// Mix speech over a background music bed, ducking the music by `duck_db`
+/// while speech is present so podcast intros/outros can be produced entirely
+/// within the crate. Speech and music must share sample rate and channels;
+/// the output is as long as the longer of the two.
+pub fn mix_with_background(
+    speech: &PcmAudio,
+    music: &PcmAudio,
+    duck_db: f32,
+) -> Result<PcmAudio, AudioError> {
+    if speech.sample_rate != music.sample_rate || speech.channels != music.channels {
+        return Err(AudioError::Processing(format!(
+            "Speech is {} Hz / {} channel(s) but music is {} Hz / {} channel(s); \
+             convert the sample format first",
+            speech.sample_rate, speech.channels, music.sample_rate, music.channels
+        )));
+    }
+
+    let channels = speech.channels.max(1) as usize;
+    let duck_factor = 10f32.powf(-duck_db.abs() / 20.0);
+    let speech_threshold = (0.01 * i16::MAX as f32) as i16;
+
+    // Smooth the duck gain so the music fades around speech instead of
+    // pumping; ~50ms time constant
+    let smoothing = 1.0 / (0.05 * speech.sample_rate as f32);
+
+    let total = speech.samples.len().max(music.samples.len());
+    let mut mixed = Vec::with_capacity(total);
+    let mut gain = 1.0f32;
+
+    for frame in 0..total.div_ceil(channels) {
+        let speech_frame = speech.samples.get(frame * channels..(frame + 1) * channels);
+        let speech_present = speech_frame
+            .map(|f| f.iter().any(|s| s.saturating_abs() > speech_threshold))
+            .unwrap_or(false);
+
+        let target = if speech_present { duck_factor } else { 1.0 };
+        gain += (target - gain) * smoothing.min(1.0);
+
+        for channel in 0..channels {
+            let index = frame * channels + channel;
+            let speech_sample = *speech.samples.get(index).unwrap_or(&0) as f32;
+            let music_sample = *music.samples.get(index).unwrap_or(&0) as f32;
+            let sum = speech_sample + music_sample * gain;
+            mixed.push(sum.clamp(i16::MIN as f32, i16::MAX as f32) as i16);
+        }
+    }
+
+    Ok(PcmAudio::new(mixed, speech.sample_rate, speech.channels))
+}
+
+/// Split long audio into chunks of at most `max_duration`, for platforms
+/// with per-file length limits.
+///
+/// Cuts are moved backwards onto the quietest frame within the last fifth of
+/// each chunk, so splits land in pauses rather than mid-word when possible.
+pub fn split_by_duration(
+    audio: &PcmAudio,
+    max_duration: Duration,
+    silence_threshold: f32,
+) -> Vec<PcmAudio> {
+    let channels = audio.channels.max(1) as usize;
+    let max_frames = ((max_duration.as_secs_f64() * audio.sample_rate as f64) as usize).max(1);
+    let total_frames = audio.samples.len() / channels;
+    let threshold_abs = (silence_threshold.clamp(0.0, 1.0) * i16::MAX as f32) as u16;
+
+    let frame_level = |frame: usize| {
+        audio.samples[frame * channels..(frame + 1) * channels]
+            .iter()
+            .map(|s| s.unsigned_abs())
+            .max()
+            .unwrap_or(0)
+    };
+
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+    while start < total_frames {
+        let remaining = total_frames - start;
+        let cut = if remaining <= max_frames {
+            total_frames
+        } else {
+            // Prefer the latest silent frame in the last 20% of the chunk,
+            // falling back to the quietest frame in that window
+            let window_start = start + max_frames - max_frames / 5;
+            let window_end = start + max_frames;
+            let boundary = (window_start..window_end)
+                .rev()
+                .find(|&frame| frame_level(frame) <= threshold_abs)
+                .unwrap_or_else(|| {
+                    (window_start..window_end)
+                        .min_by_key(|&frame| frame_level(frame))
+                        .unwrap_or(window_end)
+                });
+            boundary.max(start + 1)
+        };
+
+        chunks.push(PcmAudio::new(
+            audio.samples[start * channels..cut * channels].to_vec(),
+            audio.sample_rate,
+            audio.channels,
+        ));
+        start = cut;
+    }
+
+    chunks
+}
+
+/// Metadata describing an audio clip, as reported by [`probe`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AudioInfo {
+    pub duration: Duration,
+    pub sample_rate: u32,
+    pub channels: u16,
+    /// Average bitrate of the encoded data in bits per second
+    pub bitrate: u32,
+}
+
+/// Inspect audio data, returning duration, bitrate, sample rate, and channel
+/// count — used by the CLI for reporting and by caches for validation
+pub fn probe(audio_data: &[u8]) -> Result<AudioInfo, AudioError> {
+    let pcm = PcmAudio::decode(audio_data)?;
+    let duration = pcm.duration();
+
+    let bitrate = if duration.is_zero() {
+        0
+    } else {
+        (audio_data.len() as f64 * 8.0 / duration.as_secs_f64()) as u32
+    };
+
+    Ok(AudioInfo {
+        duration,
+        sample_rate: pcm.sample_rate,
+        channels: pcm.channels,
+        bitrate,
+    })
+}
+
+/// Inspect an audio file, see [`probe`]
+pub fn probe_file(path: &Path) -> Result<AudioInfo, AudioError> {
+    probe(&std::fs::read(path)?)
+}
+
+/// Trim leading and trailing silence, as Edge output often contains padding
+/// that makes concatenated segments and UI prompts sound loose.
+///
+/// `threshold` is the amplitude (0.0 to 1.0 of full scale) below which a
+/// frame counts as silent; up to `padding` of silence is kept at each end.
+pub fn trim_silence(audio: &PcmAudio, threshold: f32, padding: Duration) -> PcmAudio {
+    let channels = audio.channels.max(1) as usize;
+    let threshold_abs = (threshold.clamp(0.0, 1.0) * i16::MAX as f32) as i16;
+    let frames: Vec<&[i16]> = audio.samples.chunks(channels).collect();
+
+    let is_loud =
+        |frame: &&[i16]| frame.iter().any(|s| s.saturating_abs() > threshold_abs);
+    let first_loud = frames.iter().position(is_loud);
+    let last_loud = frames.iter().rposition(is_loud);
+
+    let (Some(first), Some(last)) = (first_loud, last_loud) else {
+        // Entirely silent: collapse to at most the padding length
+        let keep = padding_frames(audio, padding).min(frames.len());
+        return PcmAudio::new(
+            audio.samples[..keep * channels].to_vec(),
+            audio.sample_rate,
+            audio.channels,
+        );
+    };
+
+    let pad = padding_frames(audio, padding);
+    let start = first.saturating_sub(pad);
+    let end = (last + 1 + pad).min(frames.len());
+
+    PcmAudio::new(
+        audio.samples[start * channels..end * channels].to_vec(),
+        audio.sample_rate,
+        audio.channels,
+    )
+}
+
+fn padding_frames(audio: &PcmAudio, padding: Duration) -> usize {
+    (padding.as_secs_f64() * audio.sample_rate as f64) as usize
+}
+
+/// Generate true silence of the given duration at the given sample parameters
+pub fn silence(duration: std::time::Duration, sample_rate: u32, channels: u16) -> PcmAudio {
+    let frames = (duration.as_secs_f64() * sample_rate as f64) as usize;
+    PcmAudio::new(vec![0; frames * channels as usize], sample_rate, channels)
+}
+
+/// Join segments with a gap of true silence between them, generated at the
+/// segments' own sample rate — useful for quizzes, flashcards, and paced
+/// narration
+pub fn concat_data_with_silence(
+    segments: &[Vec<u8>],
+    gap: std::time::Duration,
+) -> Result<Vec<u8>, AudioError> {
+    let decoded: Result<Vec<_>, _> = segments.iter().map(|s| PcmAudio::decode(s)).collect();
+    concat_pcm_with_silence(&decoded?, gap)?.to_wav_bytes()
+}
+
+/// Join decoded segments into one PCM buffer with silence between them
+pub fn concat_pcm_with_silence(
+    segments: &[PcmAudio],
+    gap: std::time::Duration,
+) -> Result<PcmAudio, AudioError> {
+    let first = segments
+        .first()
+        .ok_or_else(|| AudioError::Decode("No segments to concatenate".to_string()))?;
+
+    let gap_audio = silence(gap, first.sample_rate, first.channels);
+    let mut interleaved = Vec::with_capacity(segments.len() * 2 - 1);
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 && !gap_audio.samples.is_empty() {
+            interleaved.push(gap_audio.clone());
+        }
+        interleaved.push(segment.clone());
+    }
+
+    concat_pcm(&interleaved)
+}
+
+/// Join decoded segments into one PCM buffer
+pub fn concat_pcm(segments: &[PcmAudio]) -> Result<PcmAudio, AudioError> {
+    let first = segments
+        .first()
+        .ok_or_else(|| AudioError::Decode("No segments to concatenate".to_string()))?;
+
+    let mut joined = Vec::new();
+    for (i, segment) in segments.iter().enumerate() {
+        if segment.sample_rate != first.sample_rate || segment.channels != first.channels {
+            return Err(AudioError::Decode(format!(
+                "Segment {} has {} Hz / {} channel(s), expected {} Hz / {} channel(s)",
+                i + 1,
+                segment.sample_rate,
+                segment.channels,
+                first.sample_rate,
+                first.channels
+            )));
+        }
+        joined.extend_from_slice(&segment.samples);
+    }
+
+    Ok(PcmAudio::new(joined, first.sample_rate, first.channels))
+}
+
+/// One audiobook chapter: its title and synthesized audio
+#[derive(Debug, Clone)]
+pub struct Chapter {
+    pub title: String,
+    pub audio: Vec<u8>,
+}
+
+impl Chapter {
+    pub fn new(title: String, audio: Vec<u8>) -> Self {
+        Self { title, audio }
+    }
+}
+
+/// Export chapters as a single chaptered M4B audiobook with metadata — the
+/// natural end product of the long-text pipeline.
+///
+/// AAC encoding and MP4 muxing are delegated to `ffmpeg`, which must be on
+/// the PATH (the same approach the client takes for `edge-tts`).
+pub async fn export_audiobook(
+    chapters: &[Chapter],
+    tags: &AudioTags,
+    output: &Path,
+) -> Result<(), AudioError> {
+    use tokio::process::Command;
+
+    if chapters.is_empty() {
+        return Err(AudioError::Processing(
+            "No chapters to export".to_string(),
+        ));
+    }
+
+    let work_dir = std::env::temp_dir().join(format!("tts_audiobook_{}", uuid::Uuid::new_v4()));
+    tokio::fs::create_dir_all(&work_dir).await?;
+
+    // Write chapter audio to disk and measure durations for chapter markers
+    let mut chapter_files = Vec::new();
+    let mut durations = Vec::new();
+    for (i, chapter) in chapters.iter().enumerate() {
+        let path = work_dir.join(format!("chapter_{:04}.mp3", i + 1));
+        tokio::fs::write(&path, &chapter.audio).await?;
+        durations.push(PcmAudio::decode(&chapter.audio)?.duration());
+        chapter_files.push(path);
+    }
+
+    let list_path = work_dir.join("chapters.txt");
+    let list: String = chapter_files
+        .iter()
+        .map(|p| format!("file '{}'\n", p.display()))
+        .collect();
+    tokio::fs::write(&list_path, list).await?;
+
+    let metadata_path = work_dir.join("metadata.txt");
+    tokio::fs::write(&metadata_path, build_ffmetadata(chapters, &durations, tags)).await?;
+
+    let result = Command::new("ffmpeg")
+        .args([
+            "-y",
+            "-f",
+            "concat",
+            "-safe",
+            "0",
+            "-i",
+            list_path.to_str().unwrap(),
+            "-i",
+            metadata_path.to_str().unwrap(),
+            "-map_metadata",
+            "1",
+            "-c:a",
+            "aac",
+            "-b:a",
+            "64k",
+            output.to_str().unwrap(),
+        ])
+        .output()
+        .await;
+
+    let _ = tokio::fs::remove_dir_all(&work_dir).await;
+
+    let output_result = result
+        .map_err(|e| AudioError::Processing(format!("Failed to execute ffmpeg: {}", e)))?;
+    if !output_result.status.success() {
+        let stderr = String::from_utf8_lossy(&output_result.stderr);
+        return Err(AudioError::Processing(format!(
+            "ffmpeg audiobook export failed: {}",
+            stderr
+        )));
+    }
+
+    Ok(())
+}
+
+/// Build an FFMETADATA1 document with global tags and chapter markers
+fn build_ffmetadata(chapters: &[Chapter], durations: &[Duration], tags: &AudioTags) -> String {
+    let mut metadata = String::from(";FFMETADATA1\n");
+    if let Some(title) = &tags.title {
+        metadata.push_str(&format!("title={}\n", escape_ffmetadata(title)));
+    }
+    if let Some(artist) = &tags.artist {
+        metadata.push_str(&format!("artist={}\n", escape_ffmetadata(artist)));
+    }
+    if let Some(album) = &tags.album {
+        metadata.push_str(&format!("album={}\n", escape_ffmetadata(album)));
+    }
+    metadata.push_str("genre=Audiobook\n");
+
+    let mut start_ms = 0u128;
+    for (chapter, duration) in chapters.iter().zip(durations) {
+        let end_ms = start_ms + duration.as_millis();
+        metadata.push_str("[CHAPTER]\nTIMEBASE=1/1000\n");
+        metadata.push_str(&format!("START={}\n", start_ms));
+        metadata.push_str(&format!("END={}\n", end_ms));
+        metadata.push_str(&format!("title={}\n", escape_ffmetadata(&chapter.title)));
+        start_ms = end_ms;
+    }
+
+    metadata
+}
+
+/// Escape characters with special meaning in FFMETADATA values
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(crate) fn tone(sample_rate: u32, channels: u16, frames: usize, value: i16) -> PcmAudio {
+        PcmAudio::new(
+            vec![value; frames * channels as usize],
+            sample_rate,
+            channels,
+        )
+    }
+
+    #[test]
+    fn test_wav_round_trip() {
+        let original = tone(16000, 1, 1600, 1000);
+        let wav = original.to_wav_bytes().unwrap();
+        let decoded = PcmAudio::decode(&wav).unwrap();
+
+        assert_eq!(decoded.sample_rate, 16000);
+        assert_eq!(decoded.channels, 1);
+        assert_eq!(decoded.samples, original.samples);
+    }
+
+    #[tokio::test]
+    async fn test_transcode_wav_to_pcm_and_back() {
+        let original = tone(16000, 1, 100, 1234);
+        let wav = original.to_wav_bytes().unwrap();
+        let pcm_format = AudioFormat::Pcm {
+            sample_rate: 16000,
+            channels: 1,
+        };
+
+        let raw = transcode(&wav, AudioFormat::Wav, pcm_format).await.unwrap();
+        assert_eq!(raw.len(), original.samples.len() * 2);
+
+        let back = transcode(&raw, pcm_format, AudioFormat::Wav).await.unwrap();
+        assert_eq!(PcmAudio::decode(&back).unwrap().samples, original.samples);
+    }
+
+    #[tokio::test]
+    async fn test_transcode_rejects_mismatched_pcm_target() {
+        let wav = tone(16000, 1, 100, 0).to_wav_bytes().unwrap();
+        let result = transcode(
+            &wav,
+            AudioFormat::Wav,
+            AudioFormat::Pcm {
+                sample_rate: 8000,
+                channels: 1,
+            },
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_to_wav_produces_riff_output() {
+        let source = tone(16000, 1, 100, 1000).to_wav_bytes().unwrap();
+        let wav = to_wav(&source).unwrap();
+        assert!(wav.starts_with(b"RIFF"));
+    }
+
+    #[test]
+    fn test_concat_data() {
+        let a = tone(16000, 1, 100, 500).to_wav_bytes().unwrap();
+        let b = tone(16000, 1, 50, -500).to_wav_bytes().unwrap();
+
+        let joined = concat_data(&[a, b]).unwrap();
+        let decoded = PcmAudio::decode(&joined).unwrap();
+        assert_eq!(decoded.samples.len(), 150);
+    }
+
+    #[test]
+    fn test_build_ffmetadata_chapter_markers() {
+        let chapters = vec![
+            Chapter::new("Intro".to_string(), Vec::new()),
+            Chapter::new("Part; 1".to_string(), Vec::new()),
+        ];
+        let durations = vec![Duration::from_millis(1500), Duration::from_millis(2500)];
+        let tags = AudioTags {
+            title: Some("My Book".to_string()),
+            ..AudioTags::default()
+        };
+
+        let metadata = build_ffmetadata(&chapters, &durations, &tags);
+        assert!(metadata.starts_with(";FFMETADATA1\n"));
+        assert!(metadata.contains("title=My Book"));
+        assert!(metadata.contains("START=0\nEND=1500\ntitle=Intro"));
+        assert!(metadata.contains("START=1500\nEND=4000\ntitle=Part\\; 1"));
+    }
+
+    #[test]
+    fn test_normalize_loudness_adjusts_level() {
+        // 5s of a 440-ish square wave, loud enough to measure
+        let sample_rate = 16000u32;
+        let samples: Vec<i16> = (0..sample_rate as usize * 5)
+            .map(|i| if (i / 18) % 2 == 0 { 8000 } else { -8000 })
+            .collect();
+        let audio = PcmAudio::new(samples, sample_rate, 1);
+
+        let normalized = normalize_loudness(&audio, -30.0).unwrap();
+        let loudness = measure_loudness(&normalized).unwrap();
+        assert!((loudness - (-30.0)).abs() < 1.0, "got {} LUFS", loudness);
+    }
+
+    #[test]
+    fn test_normalize_loudness_leaves_silence_alone() {
+        let quiet = silence(std::time::Duration::from_secs(1), 16000, 1);
+        let normalized = normalize_loudness(&quiet, -16.0).unwrap();
+        assert_eq!(normalized, quiet);
+    }
+
+    #[test]
+    fn test_convert_format_resamples() {
+        let audio = tone(16000, 1, 16000, 1000); // 1 second
+        let converted = convert_format(&audio, 8000, 1);
+
+        assert_eq!(converted.sample_rate, 8000);
+        assert_eq!(converted.samples.len(), 8000);
+        assert_eq!(converted.duration(), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_convert_format_mono_to_stereo() {
+        let audio = PcmAudio::new(vec![100, 200, 300], 16000, 1);
+        let stereo = convert_format(&audio, 16000, 2);
+        assert_eq!(stereo.samples, vec![100, 100, 200, 200, 300, 300]);
+    }
+
+    #[test]
+    fn test_convert_format_stereo_to_mono_averages() {
+        let audio = PcmAudio::new(vec![100, 300, -100, 100], 16000, 2);
+        let mono = convert_format(&audio, 16000, 1);
+        assert_eq!(mono.samples, vec![200, 0]);
+    }
+
+    #[test]
+    fn test_mix_with_background_ducks_music_under_speech() {
+        let sample_rate = 16000u32;
+        // 1s of speech followed by 1s of silence
+        let mut speech_samples = vec![8000i16; 16000];
+        speech_samples.extend(vec![0i16; 16000]);
+        let speech = PcmAudio::new(speech_samples, sample_rate, 1);
+        let music = tone(sample_rate, 1, 32000, 4000);
+
+        let mixed = mix_with_background(&speech, &music, 12.0).unwrap();
+        assert_eq!(mixed.samples.len(), 32000);
+
+        // Well into the speech region the music is ducked by ~12dB
+        let during_speech = mixed.samples[8000] as f32;
+        assert!(during_speech < 8000.0 + 4000.0 * 0.5);
+        // Well after speech ends the music has recovered to full level
+        assert!((mixed.samples[31000] as f32 - 4000.0).abs() < 400.0);
+    }
+
+    #[test]
+    fn test_mix_with_background_rejects_mismatch() {
+        let speech = tone(16000, 1, 100, 1000);
+        let music = tone(44100, 2, 100, 1000);
+        assert!(mix_with_background(&speech, &music, 10.0).is_err());
+    }
+
+    #[test]
+    fn test_split_by_duration_respects_limit() {
+        let audio = tone(16000, 1, 16000 * 5, 4000); // 5 seconds
+        let chunks = split_by_duration(&audio, Duration::from_secs(2), 0.01);
+
+        assert!(chunks.len() >= 3);
+        let total: usize = chunks.iter().map(|c| c.samples.len()).sum();
+        assert_eq!(total, audio.samples.len());
+        assert!(chunks
+            .iter()
+            .all(|c| c.duration() <= Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_split_by_duration_prefers_silence() {
+        // 1.5s tone, 0.2s silence, 1.5s tone at 16kHz
+        let mut samples = vec![4000i16; 24000];
+        samples.extend(vec![0i16; 3200]);
+        samples.extend(vec![4000i16; 24000]);
+        let audio = PcmAudio::new(samples, 16000, 1);
+
+        let chunks = split_by_duration(&audio, Duration::from_secs(2), 0.01);
+        assert_eq!(chunks.len(), 2);
+        // The first cut should land inside the silent gap
+        assert_eq!(*chunks[0].samples.last().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_split_short_audio_is_single_chunk() {
+        let audio = tone(16000, 1, 100, 1000);
+        let chunks = split_by_duration(&audio, Duration::from_secs(10), 0.01);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_probe_reports_audio_parameters() {
+        let wav = tone(16000, 2, 16000, 1000).to_wav_bytes().unwrap();
+        let info = probe(&wav).unwrap();
+
+        assert_eq!(info.sample_rate, 16000);
+        assert_eq!(info.channels, 2);
+        assert_eq!(info.duration, Duration::from_secs(1));
+        // 16-bit stereo at 16kHz is ~512kbps plus header overhead
+        assert!(info.bitrate >= 512_000);
+    }
+
+    #[test]
+    fn test_trim_silence_removes_padding() {
+        let mut samples = vec![0i16; 1600]; // 100ms lead-in at 16kHz
+        samples.extend(vec![5000i16; 160]);
+        samples.extend(vec![0i16; 1600]);
+        let audio = PcmAudio::new(samples, 16000, 1);
+
+        let trimmed = trim_silence(&audio, 0.01, Duration::ZERO);
+        assert_eq!(trimmed.samples.len(), 160);
+        assert!(trimmed.samples.iter().all(|&s| s == 5000));
+    }
+
+    #[test]
+    fn test_trim_silence_keeps_requested_padding() {
+        let mut samples = vec![0i16; 1600];
+        samples.extend(vec![5000i16; 160]);
+        samples.extend(vec![0i16; 1600]);
+        let audio = PcmAudio::new(samples, 16000, 1);
+
+        // 50ms padding = 800 frames kept on each side
+        let trimmed = trim_silence(&audio, 0.01, Duration::from_millis(50));
+        assert_eq!(trimmed.samples.len(), 800 + 160 + 800);
+    }
+
+    #[test]
+    fn test_trim_silence_all_silent_input() {
+        let audio = silence(Duration::from_secs(1), 16000, 1);
+        let trimmed = trim_silence(&audio, 0.01, Duration::from_millis(10));
+        assert_eq!(trimmed.samples.len(), 160);
+    }
+
+    #[test]
+    fn test_fingerprint_is_deterministic() {
+        let audio = tone(16000, 1, 1000, 1234);
+        assert_eq!(fingerprint(&audio), fingerprint(&audio));
+        assert_eq!(fingerprint(&audio).len(), 64);
+    }
+
+    #[test]
+    fn test_fingerprint_detects_content_changes() {
+        let a = tone(16000, 1, 1000, 1234);
+        let b = tone(16000, 1, 1000, 1235);
+        let c = tone(24000, 1, 1000, 1234);
+
+        assert_ne!(fingerprint(&a), fingerprint(&b));
+        assert_ne!(fingerprint(&a), fingerprint(&c));
+    }
+
+    #[test]
+    fn test_fingerprint_survives_wav_round_trip() {
+        let audio = tone(16000, 2, 1000, 1234);
+        let wav = audio.to_wav_bytes().unwrap();
+        assert_eq!(fingerprint_data(&wav).unwrap(), fingerprint(&audio));
+    }
+
+    #[test]
+    fn test_compute_peaks_bucket_count() {
+        let audio = tone(16000, 1, 16000, 1000);
+        let peaks = compute_peaks(&audio, 200);
+        assert_eq!(peaks.len(), 200);
+        assert!(peaks.iter().all(|p| p.min <= p.max));
+    }
+
+    #[test]
+    fn test_compute_peaks_tracks_amplitude() {
+        let mut samples = vec![0i16; 800];
+        samples.extend(vec![i16::MAX; 800]);
+        let peaks = compute_peaks(&PcmAudio::new(samples, 16000, 1), 2);
+
+        assert!(peaks[0].max.abs() < 0.01);
+        assert!((peaks[1].max - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_compute_peaks_empty_input() {
+        assert!(compute_peaks(&PcmAudio::new(Vec::new(), 16000, 1), 10).is_empty());
+        assert!(compute_peaks(&tone(16000, 1, 100, 0), 0).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_constant_signal() {
+        let audio = tone(16000, 1, 1000, i16::MAX / 2);
+        let report = analyze(&audio);
+
+        assert!((report.peak - 0.5).abs() < 0.01);
+        assert!((report.rms - 0.5).abs() < 0.01);
+        assert_eq!(report.clipped_samples, 0);
+        assert_eq!(report.clipping_ratio, 0.0);
+    }
+
+    #[test]
+    fn test_analyze_counts_clipped_samples() {
+        let mut samples = vec![0i16; 900];
+        samples.extend(vec![i16::MAX; 100]);
+        let report = analyze(&PcmAudio::new(samples, 16000, 1));
+
+        assert_eq!(report.clipped_samples, 100);
+        assert!((report.clipping_ratio - 0.1).abs() < 1e-6);
+        assert!((report.peak - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_analyze_empty_audio() {
+        let report = analyze(&PcmAudio::new(Vec::new(), 16000, 1));
+        assert_eq!(report.peak, 0.0);
+        assert_eq!(report.rms, 0.0);
+        assert_eq!(report.clipped_samples, 0);
+    }
+
+    #[test]
+    fn test_silence_generation() {
+        let quiet = silence(std::time::Duration::from_millis(500), 16000, 2);
+        assert_eq!(quiet.samples.len(), 8000 * 2);
+        assert!(quiet.samples.iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_concat_with_silence_inserts_gaps() {
+        let a = tone(16000, 1, 100, 500);
+        let b = tone(16000, 1, 100, 500);
+
+        let joined =
+            concat_pcm_with_silence(&[a, b], std::time::Duration::from_millis(100)).unwrap();
+        // 100 + 1600 silence + 100 samples
+        assert_eq!(joined.samples.len(), 100 + 1600 + 100);
+        assert!(joined.samples[100..1700].iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn test_concat_rejects_mismatched_segments() {
+        let a = tone(16000, 1, 100, 0);
+        let b = tone(24000, 1, 100, 0);
+        assert!(concat_pcm(&[a, b]).is_err());
+    }
+
+    #[test]
+    fn test_concat_empty_input() {
+        assert!(concat_pcm(&[]).is_err());
+    }
+}
@@ -0,0 +1,149 @@
+//! API-key checking and per-key rate limiting shared by the optional
+//! REST ([`crate::server`]) and gRPC front-ends.
+//!
+//! Keys come from [`TTSConfig::server_api_keys`]
+//! (`crate::tts_client::TTSConfig`); with none configured every request
+//! passes, so local single-user setups need no ceremony. Quotas use a
+//! fixed one-minute window per key, which is coarse but cheap and good
+//! enough to keep one consumer from monopolizing a shared service.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::tts_client::ServerApiKey;
+
+/// Why a request was turned away
+#[derive(Debug, PartialEq, Eq)]
+pub enum AuthError {
+    /// No key presented, or one that matches no configured consumer
+    Unauthorized,
+    /// The key's per-minute quota is used up
+    RateLimited {
+        /// The consumer's configured name, for the error message
+        name: String,
+        /// Seconds until the current window rolls over
+        retry_after_secs: u64,
+    },
+}
+
+impl std::fmt::Display for AuthError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuthError::Unauthorized => write!(f, "Missing or unknown API key"),
+            AuthError::RateLimited { name, .. } => {
+                write!(f, "Rate limit exceeded for '{}'", name)
+            }
+        }
+    }
+}
+
+/// Checks presented keys and tracks per-key usage
+pub struct ApiKeyGuard {
+    /// Presented key -> (consumer name, requests per minute; 0 unlimited)
+    keys: HashMap<String, (String, u32)>,
+    /// Consumer name -> (minute window, requests seen in it)
+    counters: Mutex<HashMap<String, (u64, u32)>>,
+}
+
+impl ApiKeyGuard {
+    pub fn new(api_keys: &[ServerApiKey]) -> Self {
+        Self {
+            keys: api_keys
+                .iter()
+                .map(|k| (k.key.clone(), (k.name.clone(), k.requests_per_minute)))
+                .collect(),
+            counters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Whether authentication is disabled (no keys configured)
+    pub fn is_open(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Strip the `Bearer ` prefix from an Authorization header value
+    pub fn bearer(header: Option<&str>) -> Option<&str> {
+        header?.strip_prefix("Bearer ").map(str::trim)
+    }
+
+    /// Validate a presented token and charge one request against its
+    /// quota; `None` only passes when no keys are configured
+    pub fn check(&self, presented: Option<&str>) -> Result<(), AuthError> {
+        if self.keys.is_empty() {
+            return Ok(());
+        }
+        let (name, per_minute) = presented
+            .and_then(|token| self.keys.get(token))
+            .ok_or(AuthError::Unauthorized)?;
+        if *per_minute == 0 {
+            return Ok(());
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let minute = now / 60;
+        let mut counters = self.counters.lock().unwrap();
+        let window = counters.entry(name.clone()).or_insert((minute, 0));
+        if window.0 != minute {
+            *window = (minute, 0);
+        }
+        if window.1 >= *per_minute {
+            return Err(AuthError::RateLimited {
+                name: name.clone(),
+                retry_after_secs: 60 - (now % 60),
+            });
+        }
+        window.1 += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(name: &str, key: &str, per_minute: u32) -> ServerApiKey {
+        ServerApiKey {
+            name: name.to_string(),
+            key: key.to_string(),
+            requests_per_minute: per_minute,
+        }
+    }
+
+    #[test]
+    fn test_open_when_no_keys_configured() {
+        let guard = ApiKeyGuard::new(&[]);
+        assert!(guard.is_open());
+        assert_eq!(guard.check(None), Ok(()));
+        assert_eq!(guard.check(Some("anything")), Ok(()));
+    }
+
+    #[test]
+    fn test_rejects_missing_and_unknown_keys() {
+        let guard = ApiKeyGuard::new(&[key("app", "s3cret", 0)]);
+        assert_eq!(guard.check(None), Err(AuthError::Unauthorized));
+        assert_eq!(guard.check(Some("wrong")), Err(AuthError::Unauthorized));
+        assert_eq!(guard.check(Some("s3cret")), Ok(()));
+    }
+
+    #[test]
+    fn test_rate_limit_exhausts_within_window() {
+        let guard = ApiKeyGuard::new(&[key("app", "s3cret", 2)]);
+        assert_eq!(guard.check(Some("s3cret")), Ok(()));
+        assert_eq!(guard.check(Some("s3cret")), Ok(()));
+        match guard.check(Some("s3cret")) {
+            Err(AuthError::RateLimited { name, .. }) => assert_eq!(name, "app"),
+            other => panic!("expected rate limit, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_bearer_prefix_stripping() {
+        assert_eq!(ApiKeyGuard::bearer(Some("Bearer abc")), Some("abc"));
+        assert_eq!(ApiKeyGuard::bearer(Some("abc")), None);
+        assert_eq!(ApiKeyGuard::bearer(None), None);
+    }
+}
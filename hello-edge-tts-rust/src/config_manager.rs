@@ -5,6 +5,51 @@ use std::path::Path;
 /// Configuration manager with preset support
 pub struct ConfigManager;
 
+/// Which layer of the precedence chain a configuration value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigLayer {
+    Default,
+    File,
+    Environment,
+    Cli,
+}
+
+impl ConfigLayer {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigLayer::Default => "default",
+            ConfigLayer::File => "file",
+            ConfigLayer::Environment => "environment",
+            ConfigLayer::Cli => "cli",
+        }
+    }
+}
+
+/// The result of merging all configuration layers, including which layer
+/// each field's value came from — for debugging surprising behavior
+#[derive(Debug, Clone)]
+pub struct EffectiveConfig {
+    pub config: TTSConfig,
+    /// Field name to the layer that supplied its value
+    pub sources: HashMap<String, ConfigLayer>,
+}
+
+impl EffectiveConfig {
+    /// The layer a field's value came from, defaulting to built-in
+    pub fn source(&self, field: &str) -> ConfigLayer {
+        self.sources
+            .get(field)
+            .copied()
+            .unwrap_or(ConfigLayer::Default)
+    }
+}
+
+/// Handle keeping a configuration file watch alive; dropping it stops the
+/// watch. Created by [`ConfigManager::watch`].
+pub struct ConfigWatcher {
+    _watcher: notify::RecommendedWatcher,
+}
+
 impl ConfigManager {
     const DEFAULT_CONFIG_PATHS: &'static [&'static str] =
         &["./tts_config.json", "~/.tts/config.json"];
@@ -96,6 +141,195 @@ impl ConfigManager {
         Ok(TTSConfig::default())
     }
 
+    /// Path of the first existing default config file, if any
+    pub fn find_config_file() -> Option<String> {
+        Self::DEFAULT_CONFIG_PATHS
+            .iter()
+            .map(|p| Self::expand_path(p))
+            .find(|p| Path::new(p).exists())
+    }
+
+    /// The config path writers should target: the existing file when there
+    /// is one, otherwise the first default location
+    pub fn preferred_config_path() -> String {
+        Self::find_config_file()
+            .unwrap_or_else(|| Self::expand_path(Self::DEFAULT_CONFIG_PATHS[0]))
+    }
+
+    /// Set one field in the config file at `path`, creating the file from
+    /// defaults when it does not exist. The raw value is coerced to the
+    /// field's type and the updated config is validated before anything is
+    /// written.
+    pub fn set_config_value(path: &str, key: &str, raw: &str) -> Result<TTSConfig, TTSError> {
+        let current = if Path::new(path).exists() {
+            TTSConfig::from_json_file(path)?
+        } else {
+            TTSConfig::default()
+        };
+        let mut merged = serde_json::to_value(&current)
+            .map_err(|e| TTSError::Config(format!("Failed to serialize config: {}", e)))?;
+        let existing = merged.get(key).cloned().ok_or_else(|| {
+            let mut fields: Vec<String> = merged
+                .as_object()
+                .map(|o| o.keys().cloned().collect())
+                .unwrap_or_default();
+            fields.sort();
+            TTSError::Config(format!(
+                "Unknown config key '{}'. Available: {}",
+                key,
+                fields.join(", ")
+            ))
+        })?;
+        merged[key] = Self::coerce_value(raw, &existing)?;
+
+        let config: TTSConfig = serde_json::from_value(merged)
+            .map_err(|e| TTSError::Config(format!("Invalid value for '{}': {}", key, e)))?;
+        config.validate()?;
+        config.to_json_file(path)?;
+        Ok(config)
+    }
+
+    /// Environment variable prefix for configuration overrides, e.g.
+    /// `HELLO_TTS_DEFAULT_VOICE`
+    const ENV_PREFIX: &'static str = "HELLO_TTS_";
+
+    /// Merge all configuration layers with deterministic precedence —
+    /// built-in defaults < config file < environment < CLI flags — and
+    /// report which layer each value came from. `cli_overrides` maps field
+    /// names to their raw flag values.
+    pub fn effective_config(
+        config_path: Option<&str>,
+        cli_overrides: &HashMap<String, String>,
+    ) -> Result<EffectiveConfig, TTSError> {
+        let mut merged = serde_json::to_value(TTSConfig::default())
+            .map_err(|e| TTSError::Config(format!("Failed to serialize defaults: {}", e)))?;
+        let mut sources = HashMap::new();
+
+        // Layer 2: config file (explicit path, or the first default path)
+        let file_path = match config_path {
+            Some(path) => Some(path.to_string()),
+            None => Self::DEFAULT_CONFIG_PATHS
+                .iter()
+                .map(|p| Self::expand_path(p))
+                .find(|p| Path::new(p).exists()),
+        };
+        if let Some(path) = file_path {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                TTSError::Config(format!("Failed to read config file {}: {}", path, e))
+            })?;
+            let file_value: serde_json::Value = serde_json::from_str(&content)
+                .map_err(|e| TTSError::Config(format!("Invalid JSON in config file: {}", e)))?;
+            if let serde_json::Value::Object(fields) = file_value {
+                for (field, value) in fields {
+                    if merged.get(&field).is_some() {
+                        merged[&field] = value;
+                        sources.insert(field, ConfigLayer::File);
+                    }
+                }
+            }
+        }
+
+        // Layers 3 and 4: environment, then CLI flags
+        let fields: Vec<String> = merged
+            .as_object()
+            .map(|o| o.keys().cloned().collect())
+            .unwrap_or_default();
+        for field in &fields {
+            let env_key = format!("{}{}", Self::ENV_PREFIX, field.to_uppercase());
+            if let Ok(raw) = std::env::var(&env_key) {
+                merged[field] = Self::coerce_value(&raw, &merged[field])?;
+                sources.insert(field.clone(), ConfigLayer::Environment);
+            }
+            if let Some(raw) = cli_overrides.get(field) {
+                merged[field] = Self::coerce_value(raw, &merged[field])?;
+                sources.insert(field.clone(), ConfigLayer::Cli);
+            }
+        }
+
+        let config: TTSConfig = serde_json::from_value(merged)
+            .map_err(|e| TTSError::Config(format!("Invalid merged configuration: {}", e)))?;
+        config.validate()?;
+
+        Ok(EffectiveConfig { config, sources })
+    }
+
+    /// Convert a raw override string to the JSON type of the value it
+    /// replaces
+    fn coerce_value(
+        raw: &str,
+        current: &serde_json::Value,
+    ) -> Result<serde_json::Value, TTSError> {
+        use serde_json::Value;
+
+        let coerced = match current {
+            // Durations serialize as {secs, nanos}; overrides give seconds
+            Value::Object(_) => raw
+                .parse::<u64>()
+                .ok()
+                .map(|secs| serde_json::json!({ "secs": secs, "nanos": 0 })),
+            Value::Bool(_) => raw.parse::<bool>().ok().map(Value::Bool),
+            Value::Number(_) => serde_json::from_str::<Value>(raw)
+                .ok()
+                .filter(Value::is_number),
+            Value::String(_) => Some(Value::String(raw.to_string())),
+            // Optional fields: accept JSON literals, fall back to a string
+            _ => Some(serde_json::from_str::<Value>(raw).unwrap_or(Value::String(raw.to_string()))),
+        };
+        coerced.ok_or_else(|| {
+            TTSError::Config(format!("Invalid override value: {}", raw))
+        })
+    }
+
+    /// Watch a configuration file and reload it whenever it changes, so
+    /// long-running server/daemon modes pick up voice or rate changes
+    /// without restart. Each change triggers a reload and revalidation; the
+    /// subscriber receives the new config, or the error when the updated
+    /// file is invalid. The watch stops when the returned handle is dropped.
+    pub fn watch(
+        config_path: &str,
+        subscriber: impl Fn(Result<TTSConfig, TTSError>) + Send + 'static,
+    ) -> Result<ConfigWatcher, TTSError> {
+        use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+        let path = std::path::PathBuf::from(Self::expand_path(config_path));
+        if !path.exists() {
+            return Err(TTSError::Config(format!(
+                "Config file not found: {}",
+                path.display()
+            )));
+        }
+
+        let reload_path = path.clone();
+        let file_name = path.file_name().map(|n| n.to_os_string());
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            match event {
+                Ok(event)
+                    if matches!(
+                        event.kind,
+                        EventKind::Modify(_) | EventKind::Create(_)
+                    ) && event
+                        .paths
+                        .iter()
+                        .any(|p| p.file_name().map(|n| n.to_os_string()) == file_name) =>
+                {
+                    subscriber(TTSConfig::from_json_file(&reload_path.to_string_lossy()));
+                }
+                Ok(_) => {}
+                Err(e) => subscriber(Err(TTSError::Config(format!("Watch error: {}", e)))),
+            }
+        })
+        .map_err(|e| TTSError::Config(format!("Failed to create watcher: {}", e)))?;
+
+        // Watch the parent directory so editors that replace the file
+        // (write-to-temp-then-rename) do not silently break the watch
+        let target = path.parent().filter(|p| !p.as_os_str().is_empty());
+        watcher
+            .watch(target.unwrap_or(&path), RecursiveMode::NonRecursive)
+            .map_err(|e| TTSError::Config(format!("Failed to watch config file: {}", e)))?;
+
+        Ok(ConfigWatcher { _watcher: watcher })
+    }
+
     /// Get a preset configuration
     pub fn get_preset(preset_name: &str) -> Result<TTSConfig, TTSError> {
         let presets = Self::get_presets();
@@ -118,11 +352,89 @@ impl ConfigManager {
         Self::get_presets().keys().cloned().collect()
     }
 
-    /// Create a default configuration file
+    /// Default voices by locale, used to match the system locale on first
+    /// run. Languages fall back to their first entry when only the region
+    /// differs (e.g. `de-AT` matches `de-DE`).
+    const DEFAULT_VOICES_BY_LOCALE: &'static [(&'static str, &'static str)] = &[
+        ("en-US", "en-US-AriaNeural"),
+        ("en-GB", "en-GB-SoniaNeural"),
+        ("de-DE", "de-DE-KatjaNeural"),
+        ("fr-FR", "fr-FR-DeniseNeural"),
+        ("es-ES", "es-ES-ElviraNeural"),
+        ("es-MX", "es-MX-DaliaNeural"),
+        ("it-IT", "it-IT-ElsaNeural"),
+        ("pt-BR", "pt-BR-FranciscaNeural"),
+        ("pt-PT", "pt-PT-RaquelNeural"),
+        ("ja-JP", "ja-JP-NanamiNeural"),
+        ("ko-KR", "ko-KR-SunHiNeural"),
+        ("zh-CN", "zh-CN-XiaoxiaoNeural"),
+        ("zh-TW", "zh-TW-HsiaoChenNeural"),
+        ("ru-RU", "ru-RU-SvetlanaNeural"),
+        ("ar-SA", "ar-SA-ZariyahNeural"),
+        ("hi-IN", "hi-IN-SwaraNeural"),
+        ("nl-NL", "nl-NL-ColetteNeural"),
+        ("pl-PL", "pl-PL-ZofiaNeural"),
+        ("tr-TR", "tr-TR-EmelNeural"),
+        ("sv-SE", "sv-SE-SofieNeural"),
+    ];
+
+    /// Detect the system locale from the POSIX environment (`LC_ALL`,
+    /// `LC_MESSAGES`, `LANG`), normalized to BCP 47 form like "de-DE"
+    pub fn system_locale() -> Option<String> {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+            .map(|value| Self::normalize_locale(&value))
+    }
+
+    /// Normalize a POSIX locale string ("de_DE.UTF-8") to "de-DE"
+    fn normalize_locale(raw: &str) -> String {
+        raw.split(['.', '@'])
+            .next()
+            .unwrap_or(raw)
+            .replace('_', "-")
+    }
+
+    /// The default voice matching a locale — exact locale first, then the
+    /// language's first entry — or `None` for unknown languages
+    pub fn default_voice_for_locale(locale: &str) -> Option<&'static str> {
+        let normalized = Self::normalize_locale(locale);
+        let exact = Self::DEFAULT_VOICES_BY_LOCALE
+            .iter()
+            .find(|(l, _)| l.eq_ignore_ascii_case(&normalized));
+        let language = normalized.split('-').next().unwrap_or(&normalized);
+        exact
+            .or_else(|| {
+                Self::DEFAULT_VOICES_BY_LOCALE.iter().find(|(l, _)| {
+                    l.split('-')
+                        .next()
+                        .is_some_and(|lang| lang.eq_ignore_ascii_case(language))
+                })
+            })
+            .map(|(_, voice)| *voice)
+    }
+
+    /// The default voice for the detected system locale, falling back to
+    /// en-US-AriaNeural when detection fails or no voice matches
+    pub fn system_default_voice() -> String {
+        Self::system_locale()
+            .as_deref()
+            .and_then(Self::default_voice_for_locale)
+            .unwrap_or("en-US-AriaNeural")
+            .to_string()
+    }
+
+    /// Create a default configuration file, picking a default voice that
+    /// matches the system locale so non-English setups speak their own
+    /// language out of the box
     pub fn create_default_config(file_path: &str, preset: &str) -> Result<(), TTSError> {
-        let config = Self::get_preset(preset)?;
+        let mut config = Self::get_preset(preset)?;
+        if config.default_voice == TTSConfig::default().default_voice {
+            config.default_voice = Self::system_default_voice();
+        }
         config.to_json_file(file_path)?;
-        println!("Created default configuration file: {}", file_path);
+        tracing::info!("Created default configuration file: {}", file_path);
         Ok(())
     }
 
@@ -137,6 +449,92 @@ impl ConfigManager {
     }
 }
 
+/// Store and retrieve backend credentials via the OS keyring (macOS
+/// Keychain, Windows Credential Manager, Linux keyutils), so config files
+/// only reference secrets by name instead of embedding plaintext keys.
+///
+/// `TTSConfig::azure_key_secret` and `TTSConfig::custom_endpoint_key_secret`
+/// name entries in this store; [`SecretStore::azure_key`] and
+/// [`SecretStore::custom_endpoint_key`] resolve them when a backend needs
+/// the actual credential.
+pub struct SecretStore {
+    service: String,
+}
+
+impl Default for SecretStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SecretStore {
+    /// Keyring service name all entries are registered under
+    const SERVICE: &'static str = "hello-edge-tts";
+
+    pub fn new() -> Self {
+        Self {
+            service: Self::SERVICE.to_string(),
+        }
+    }
+
+    /// Use a non-default service name, e.g. to isolate test entries
+    pub fn with_service(service: &str) -> Self {
+        Self {
+            service: service.to_string(),
+        }
+    }
+
+    fn entry(&self, name: &str) -> Result<keyring::Entry, TTSError> {
+        keyring::Entry::new(&self.service, name)
+            .map_err(|e| TTSError::Config(format!("Failed to open keyring entry '{}': {}", name, e)))
+    }
+
+    /// Store a secret under the given name, replacing any existing value
+    pub fn store(&self, name: &str, secret: &str) -> Result<(), TTSError> {
+        self.entry(name)?
+            .set_password(secret)
+            .map_err(|e| TTSError::Config(format!("Failed to store secret '{}': {}", name, e)))
+    }
+
+    /// Retrieve the secret stored under the given name
+    pub fn retrieve(&self, name: &str) -> Result<String, TTSError> {
+        self.entry(name)?.get_password().map_err(|e| match e {
+            keyring::Error::NoEntry => TTSError::Config(format!(
+                "Secret '{}' not found in the OS keyring; store it first",
+                name
+            )),
+            other => TTSError::Config(format!("Failed to read secret '{}': {}", name, other)),
+        })
+    }
+
+    /// Remove the secret stored under the given name
+    pub fn delete(&self, name: &str) -> Result<(), TTSError> {
+        self.entry(name)?
+            .delete_credential()
+            .map_err(|e| TTSError::Config(format!("Failed to delete secret '{}': {}", name, e)))
+    }
+
+    /// Resolve the Azure subscription key referenced by the config, or
+    /// `None` when no secret name is configured
+    pub fn azure_key(&self, config: &TTSConfig) -> Result<Option<String>, TTSError> {
+        config
+            .azure_key_secret
+            .as_deref()
+            .map(|name| self.retrieve(name))
+            .transpose()
+    }
+
+    /// Resolve the custom endpoint's API key referenced by the config, or
+    /// `None` when no secret name is configured
+    pub fn custom_endpoint_key(&self, config: &TTSConfig) -> Result<Option<String>, TTSError> {
+        config
+            .custom_endpoint_key_secret
+            .as_deref()
+            .map(|name| self.retrieve(name))
+            .transpose()
+    }
+}
+
 /// Convenience functions
 pub fn load_config(config_path: Option<&str>) -> Result<TTSConfig, TTSError> {
     ConfigManager::load_config(config_path)
@@ -200,6 +598,122 @@ mod tests {
         assert!(presets.contains(&"slow"));
     }
 
+    #[test]
+    fn test_effective_config_precedence() {
+        let dir = std::env::temp_dir().join("hello_tts_layered_config_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        std::fs::write(&path, r#"{"rate": "+10%", "batch_size": 7}"#).unwrap();
+
+        std::env::set_var("HELLO_TTS_BATCH_SIZE", "9");
+        let mut cli = HashMap::new();
+        cli.insert("pitch".to_string(), "+5%".to_string());
+
+        let effective =
+            ConfigManager::effective_config(Some(path.to_str().unwrap()), &cli).unwrap();
+        std::env::remove_var("HELLO_TTS_BATCH_SIZE");
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(effective.config.rate, "+10%");
+        assert_eq!(effective.config.batch_size, 9);
+        assert_eq!(effective.config.pitch, "+5%");
+        assert_eq!(effective.config.default_voice, "en-US-AriaNeural");
+
+        assert_eq!(effective.source("rate"), ConfigLayer::File);
+        assert_eq!(effective.source("batch_size"), ConfigLayer::Environment);
+        assert_eq!(effective.source("pitch"), ConfigLayer::Cli);
+        assert_eq!(effective.source("default_voice"), ConfigLayer::Default);
+    }
+
+    #[test]
+    fn test_watch_reports_config_changes() {
+        let dir = std::env::temp_dir().join("hello_tts_watch_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.json");
+        TTSConfig::default().to_json_file(path.to_str().unwrap()).unwrap();
+
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let watcher = ConfigManager::watch(path.to_str().unwrap(), move |result| {
+            sender.send(result).ok();
+        })
+        .unwrap();
+
+        let updated = TTSConfig {
+            rate: "+25%".to_string(),
+            ..TTSConfig::default()
+        };
+        updated.to_json_file(path.to_str().unwrap()).unwrap();
+
+        // The first event can catch the file mid-write, so drain events
+        // until a complete reload comes through
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let reloaded = loop {
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            match receiver.recv_timeout(remaining) {
+                Ok(Ok(config)) => break config,
+                Ok(Err(_)) => continue,
+                Err(_) => panic!("no complete reload received"),
+            }
+        };
+        assert_eq!(reloaded.rate, "+25%");
+
+        drop(watcher);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_watch_missing_file_is_an_error() {
+        assert!(ConfigManager::watch("/nonexistent/config.json", |_| {}).is_err());
+    }
+
+    #[test]
+    fn test_effective_config_rejects_bad_override_type() {
+        let mut cli = HashMap::new();
+        cli.insert("batch_size".to_string(), "lots".to_string());
+        assert!(ConfigManager::effective_config(None, &cli).is_err());
+    }
+
+    #[test]
+    fn test_default_voice_for_locale() {
+        assert_eq!(
+            ConfigManager::default_voice_for_locale("de-DE"),
+            Some("de-DE-KatjaNeural")
+        );
+        // POSIX form and regional fallback
+        assert_eq!(
+            ConfigManager::default_voice_for_locale("de_AT.UTF-8"),
+            Some("de-DE-KatjaNeural")
+        );
+        assert_eq!(
+            ConfigManager::default_voice_for_locale("pt-BR"),
+            Some("pt-BR-FranciscaNeural")
+        );
+        assert_eq!(ConfigManager::default_voice_for_locale("xx-XX"), None);
+    }
+
+    #[test]
+    fn test_secret_store_resolves_nothing_when_unconfigured() {
+        let store = SecretStore::new();
+        let config = TTSConfig::default();
+        assert_eq!(store.azure_key(&config).unwrap(), None);
+        assert_eq!(store.custom_endpoint_key(&config).unwrap(), None);
+    }
+
+    #[test]
+    fn test_config_rejects_endpoint_secret_without_endpoint() {
+        let config = TTSConfig {
+            custom_endpoint_key_secret: Some("my-endpoint-key".to_string()),
+            ..TTSConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        let config = TTSConfig {
+            custom_endpoint: Some("not-a-url".to_string()),
+            ..TTSConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
     #[test]
     fn test_config_validation() {
         let mut config = TTSConfig::default();
@@ -0,0 +1,192 @@
+//! Optional gRPC front-end for the TTS client, enabled with the `grpc`
+//! feature.
+//!
+//! The service is defined in `proto/tts.proto` and offers the same
+//! operations as the REST [`crate::server`] module: unary synthesis,
+//! server-streaming synthesis for low-latency playback, and voice
+//! listing. `pb` holds the tonic-generated types, committed so building
+//! the crate does not require `protoc`; regenerate it with `tonic-build`
+//! after changing the proto.
+
+// tonic's Status is large by design; every RPC returns it
+#![allow(clippy::result_large_err)]
+
+#[allow(clippy::all)]
+pub mod pb;
+
+use futures_util::StreamExt;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tonic::{Request, Response, Status};
+
+use crate::auth::{ApiKeyGuard, AuthError};
+use crate::ssml_utils::SSMLValidator;
+use crate::tts_client::{TTSClient, TTSConfig, TTSError};
+use pb::text_to_speech_server::{TextToSpeech, TextToSpeechServer};
+
+/// gRPC implementation of [`pb::text_to_speech_server::TextToSpeech`],
+/// sharing one client so the voice cache spans requests
+pub struct TtsService {
+    client: Arc<Mutex<TTSClient>>,
+    config: TTSConfig,
+}
+
+impl TtsService {
+    pub fn new(config: TTSConfig) -> Self {
+        Self {
+            client: Arc::new(Mutex::new(TTSClient::new(Some(config.clone())))),
+            config,
+        }
+    }
+
+    /// Wrap the service for [`tonic::transport::Server::add_service`]
+    pub fn into_server(self) -> TextToSpeechServer<Self> {
+        TextToSpeechServer::new(self)
+    }
+
+    /// Resolve the request's voice through the configured aliases, with
+    /// the server default for an empty field
+    fn resolve_voice(&self, voice: &str) -> String {
+        if voice.is_empty() {
+            self.config.resolve_voice(&self.config.default_voice)
+        } else {
+            self.config.resolve_voice(voice)
+        }
+    }
+}
+
+/// Map library errors onto gRPC status codes
+fn to_status(error: TTSError) -> Status {
+    match &error {
+        TTSError::Network(_) => Status::unavailable(error.to_string()),
+        TTSError::VoiceNotFound(_) => Status::not_found(error.to_string()),
+        TTSError::Config(_) => Status::invalid_argument(error.to_string()),
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+#[tonic::async_trait]
+impl TextToSpeech for TtsService {
+    async fn synthesize(
+        &self,
+        request: Request<pb::SynthesizeRequest>,
+    ) -> Result<Response<pb::SynthesizeResponse>, Status> {
+        let request = request.into_inner();
+        let voice = self.resolve_voice(&request.voice);
+        if request.ssml {
+            let problems = SSMLValidator::validate(&request.text);
+            if !problems.is_empty() {
+                return Err(Status::invalid_argument(format!(
+                    "Invalid SSML: {}",
+                    problems.join("; ")
+                )));
+            }
+        }
+        let client = self.client.lock().await;
+        let audio = client
+            .synthesize_text(&request.text, &voice, Some(request.ssml))
+            .await
+            .map_err(to_status)?;
+        Ok(Response::new(pb::SynthesizeResponse { audio }))
+    }
+
+    type SynthesizeStreamStream =
+        Pin<Box<dyn futures_util::Stream<Item = Result<pb::AudioChunk, Status>> + Send>>;
+
+    async fn synthesize_stream(
+        &self,
+        request: Request<pb::SynthesizeRequest>,
+    ) -> Result<Response<Self::SynthesizeStreamStream>, Status> {
+        let request = request.into_inner();
+        if request.ssml {
+            return Err(Status::invalid_argument(
+                "Streaming synthesis takes plain text; use Synthesize for SSML",
+            ));
+        }
+        let voice = self.resolve_voice(&request.voice);
+        let chunks = {
+            let client = self.client.lock().await;
+            client.synthesize_stream(&request.text, &voice)
+        };
+        let stream = chunks.map(|item| {
+            item.map(|chunk| pb::AudioChunk {
+                data: chunk.to_vec(),
+            })
+            .map_err(to_status)
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn list_voices(
+        &self,
+        request: Request<pb::ListVoicesRequest>,
+    ) -> Result<Response<pb::ListVoicesResponse>, Status> {
+        let language = request.into_inner().language;
+        let voices = self
+            .client
+            .lock()
+            .await
+            .list_voices()
+            .await
+            .map_err(to_status)?;
+        let voices = voices
+            .into_iter()
+            .filter(|voice| language.is_empty() || voice.matches_language(&language))
+            .map(|voice| pb::Voice {
+                name: voice.name,
+                display_name: voice.display_name,
+                locale: voice.locale,
+                gender: voice.gender,
+            })
+            .collect();
+        Ok(Response::new(pb::ListVoicesResponse { voices }))
+    }
+}
+
+/// Interceptor enforcing [`TTSConfig::server_api_keys`]: the key rides
+/// the `authorization` metadata as a `Bearer` token (or `x-api-key`),
+/// and each key's per-minute quota applies across all RPCs
+pub fn api_key_interceptor(
+    guard: Arc<ApiKeyGuard>,
+) -> impl FnMut(Request<()>) -> Result<Request<()>, Status> + Clone {
+    move |request: Request<()>| {
+        let verdict = {
+            let metadata = request.metadata();
+            let token = metadata
+                .get("authorization")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| ApiKeyGuard::bearer(Some(value)))
+                .or_else(|| metadata.get("x-api-key").and_then(|value| value.to_str().ok()));
+            guard.check(token)
+        };
+        match verdict {
+            Ok(()) => Ok(request),
+            Err(error @ AuthError::Unauthorized) => {
+                Err(Status::unauthenticated(error.to_string()))
+            }
+            Err(error @ AuthError::RateLimited { .. }) => {
+                Err(Status::resource_exhausted(error.to_string()))
+            }
+        }
+    }
+}
+
+/// Bind `addr` and serve the gRPC service until the task is cancelled
+pub async fn serve(addr: std::net::SocketAddr, config: TTSConfig) -> Result<(), TTSError> {
+    let guard = Arc::new(ApiKeyGuard::new(&config.server_api_keys));
+    if guard.is_open() {
+        tracing::warn!("No server_api_keys configured — the gRPC server is open to all callers");
+    }
+    tracing::info!("gRPC server listening on {}", addr);
+    let service = pb::text_to_speech_server::TextToSpeechServer::with_interceptor(
+        TtsService::new(config),
+        api_key_interceptor(guard),
+    );
+    tonic::transport::Server::builder()
+        .add_service(service)
+        .serve(addr)
+        .await
+        .map_err(|e| TTSError::Config(format!("gRPC server failed: {}", e)))?;
+    Ok(())
+}
@@ -4,22 +4,38 @@
 //! demonstrating text-to-speech functionality with audio playback capabilities.
 
 pub mod audio_player;
+pub mod audio_processing;
+#[cfg(any(feature = "server", feature = "grpc"))]
+pub mod auth;
 pub mod config_manager;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "server")]
+pub mod server;
 pub mod ssml_utils;
 pub mod tts_client;
 
-pub use audio_player::{AudioError, AudioPlayer};
+pub use audio_processing::PcmAudio;
+pub use audio_player::{
+    AudioError, AudioFormat, AudioMixer, AudioPlayer, DspStage, GainStage, LimiterStage,
+    PlaybackObserver, ShelfStage,
+};
 pub use config_manager::{
-    create_default_config, get_preset, list_presets, load_config, ConfigManager,
+    create_default_config, get_preset, list_presets, load_config, ConfigLayer, ConfigManager,
+    EffectiveConfig, SecretStore,
 };
 pub use ssml_utils::{SSMLBuilder, SSMLTemplates, SSMLValidator};
-pub use tts_client::{TTSClient, TTSConfig, TTSError, Voice};
+pub use tts_client::{
+    boundaries_to_srt, boundaries_to_vtt, AudioTags, Bookmark, ProgressReporter, ServerApiKey,
+    SubtitleCue, TTSClient, TTSConfig, TTSError, Voice, WordBoundary,
+};
 
 /// Re-export commonly used types
 pub mod prelude {
     pub use crate::{
-        create_default_config, get_preset, list_presets, load_config, AudioError, AudioPlayer,
-        ConfigManager, SSMLBuilder, SSMLTemplates, SSMLValidator, TTSClient, TTSConfig, TTSError,
-        Voice,
+        boundaries_to_srt, boundaries_to_vtt, create_default_config, get_preset, list_presets,
+        load_config, AudioError, AudioPlayer, AudioFormat, ConfigManager, PlaybackObserver,
+        ProgressReporter, SSMLBuilder, SSMLTemplates, SSMLValidator, TTSClient, TTSConfig,
+        TTSError, Voice, WordBoundary,
     };
 }
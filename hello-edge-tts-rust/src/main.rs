@@ -9,13 +9,285 @@ use std::path::PathBuf;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Emit structured JSON on stdout instead of decorated text, so the
+    /// CLI composes with scripts and jq
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Suppress everything but errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Increase log verbosity: --verbose for info, twice for debug with
+    /// wire logs (-v stays reserved for --voice on subcommands)
+    #[arg(long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+}
+
+/// Output audio encoding selected by `--format`
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum OutputFormat {
+    Mp3,
+    Wav,
+    Ogg,
+    Pcm,
+}
+
+impl OutputFormat {
+    fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Mp3 => "mp3",
+            OutputFormat::Wav => "wav",
+            OutputFormat::Ogg => "ogg",
+            OutputFormat::Pcm => "pcm",
+        }
+    }
+}
+
+/// Voice catalog export format selected by `voices --export`
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+    Md,
+}
+
+impl ExportFormat {
+    fn render(self, voices: &[Voice]) -> Result<String, Box<dyn std::error::Error>> {
+        Ok(match self {
+            ExportFormat::Json => serde_json::to_string_pretty(voices)?,
+            ExportFormat::Csv => {
+                let escape = |field: &str| {
+                    if field.contains([',', '"', '\n']) {
+                        format!("\"{}\"", field.replace('"', "\"\""))
+                    } else {
+                        field.to_string()
+                    }
+                };
+                let mut csv = String::from("name,display_name,locale,gender\n");
+                for voice in voices {
+                    csv.push_str(&format!(
+                        "{},{},{},{}\n",
+                        escape(&voice.name),
+                        escape(&voice.display_name),
+                        escape(&voice.locale),
+                        escape(&voice.gender)
+                    ));
+                }
+                csv
+            }
+            ExportFormat::Md => {
+                let mut md = String::from(
+                    "| Name | Display name | Locale | Gender |\n|------|--------------|--------|--------|\n",
+                );
+                for voice in voices {
+                    md.push_str(&format!(
+                        "| {} | {} | {} | {} |\n",
+                        voice.name, voice.display_name, voice.locale, voice.gender
+                    ));
+                }
+                md
+            }
+        })
+    }
+}
+
+/// Caption file format written by `--subtitles`
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum SubtitleFormat {
+    Srt,
+    Vtt,
+}
+
+impl SubtitleFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            SubtitleFormat::Srt => "srt",
+            SubtitleFormat::Vtt => "vtt",
+        }
+    }
+
+    fn render(self, text: &str) -> String {
+        let boundaries = hello_edge_tts::ssml_utils::estimate_word_boundaries(text);
+        match self {
+            SubtitleFormat::Srt => boundaries_to_srt(&boundaries),
+            SubtitleFormat::Vtt => boundaries_to_vtt(&boundaries),
+        }
+    }
+}
+
+/// Bridge from the library's [`ProgressReporter`] to an indicatif bar
+struct BarReporter {
+    bar: indicatif::ProgressBar,
+}
+
+impl BarReporter {
+    fn new(label: &str) -> Self {
+        let bar = indicatif::ProgressBar::new(1);
+        bar.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{prefix} [{bar:30}] {pos}/{len} {msg} (ETA {eta})",
+            )
+            .expect("valid progress template")
+            .progress_chars("=> "),
+        );
+        bar.set_prefix(label.to_string());
+        Self { bar }
+    }
+
+    fn finish(&self) {
+        self.bar.finish_and_clear();
+    }
+}
+
+impl ProgressReporter for BarReporter {
+    fn on_progress(&self, done: usize, total: usize, bytes: u64) {
+        self.bar.set_length(total as u64);
+        self.bar.set_position(done as u64);
+        self.bar
+            .set_message(format!("{}", indicatif::HumanBytes(bytes)));
+    }
+}
+
+/// Write the caption file next to an audio output, swapping the extension
+fn write_subtitles(
+    format: SubtitleFormat,
+    text: &str,
+    audio_path: &std::path::Path,
+) -> std::io::Result<PathBuf> {
+    let path = audio_path.with_extension(format.extension());
+    std::fs::write(&path, format.render(text))?;
+    Ok(path)
+}
+
+/// Arguments for the `speak` subcommand
+#[derive(clap::Args)]
+struct SpeakArgs {
+    /// Text to convert to speech; '-' reads from stdin
+    #[arg(
+        short,
+        long,
+        required_unless_present_any = ["file", "clipboard"],
+        conflicts_with = "file"
+    )]
+    text: Option<String>,
+
+    /// Read the text from a UTF-8 file instead; long files are chunked
+    /// at sentence boundaries
+    #[arg(short, long)]
+    file: Option<PathBuf>,
+
+    /// Read the text from the system clipboard, for a quick
+    /// "read selection aloud"
+    #[arg(long, conflicts_with_all = ["text", "file"])]
+    clipboard: bool,
+
+    /// Voice to use for synthesis
+    #[arg(short, long, default_value = "en-US-AriaNeural")]
+    voice: String,
+
+    /// Treat the input as SSML: validate it (with line/column errors)
+    /// and send it as-is instead of wrapping plain text
+    #[arg(long)]
+    ssml: bool,
+
+    /// Write a caption file next to the audio output
+    #[arg(long, value_enum)]
+    subtitles: Option<SubtitleFormat>,
+
+    /// Speaking rate as a signed percentage, e.g. '+20%' or '-10%'
+    #[arg(long)]
+    rate: Option<String>,
+
+    /// Pitch shift as a signed percentage, e.g. '+10%' or '-5%'
+    #[arg(long)]
+    pitch: Option<String>,
+
+    /// Volume from 0% to 200%, e.g. '80%'
+    #[arg(long)]
+    volume: Option<String>,
+
+    /// Output audio encoding; the file extension follows automatically
+    #[arg(long, value_enum)]
+    format: Option<OutputFormat>,
+
+    /// Output file path; '-' streams the audio to stdout for piping
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Play audio after synthesis
+    #[arg(short, long, default_value = "true")]
+    play: bool,
+
+    /// Play while synthesizing: audio starts after the first chunk is
+    /// ready instead of after the whole input, so nothing is saved
+    #[arg(long, conflicts_with_all = ["output", "subtitles", "ssml", "dry_run"])]
+    stream: bool,
+
+    /// Show the resolved voice, SSML, format, and target paths without
+    /// synthesizing anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Indent SSML one tag per line so `--dry-run` output is readable
+fn pretty_print_ssml(ssml: &str) -> String {
+    let mut out = String::new();
+    let mut depth = 0usize;
+    let mut rest = ssml.trim();
+    while let Some(start) = rest.find('<') {
+        let text = rest[..start].trim();
+        if !text.is_empty() {
+            out.push_str(&"  ".repeat(depth));
+            out.push_str(text);
+            out.push('\n');
+        }
+        let Some(end) = rest[start..].find('>').map(|e| start + e) else {
+            break;
+        };
+        let tag = &rest[start..=end];
+        let closing = tag.starts_with("</");
+        let self_closing = tag.ends_with("/>") || tag.starts_with("<?");
+        if closing {
+            depth = depth.saturating_sub(1);
+        }
+        out.push_str(&"  ".repeat(depth));
+        out.push_str(tag);
+        out.push('\n');
+        if !closing && !self_closing {
+            depth += 1;
+        }
+        rest = &rest[end + 1..];
+    }
+    let tail = rest.trim();
+    if !tail.is_empty() {
+        out.push_str(tail);
+        out.push('\n');
+    }
+    out
 }
 
+/// Actions for the `template` subcommand
 #[derive(Subcommand)]
-enum Commands {
-    /// Convert text to speech
-    Speak {
-        /// Text to convert to speech
+enum TemplateAction {
+    /// List built-in and user-registered templates
+    List,
+    /// Show the SSML a template produces for a sample text
+    Show {
+        /// Template name
+        name: String,
+
+        /// Sample text to render the template with
+        #[arg(short, long, default_value = "Hello from hello-edge-tts")]
+        text: String,
+    },
+    /// Synthesize text through a template
+    Apply {
+        /// Template name
+        name: String,
+
+        /// Text to speak; becomes the {text} placeholder in user templates
         #[arg(short, long)]
         text: String,
 
@@ -23,14 +295,56 @@ enum Commands {
         #[arg(short, long, default_value = "en-US-AriaNeural")]
         voice: String,
 
+        /// Values for other {placeholder}s in user templates
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        values: Vec<String>,
+
         /// Output file path
         #[arg(short, long)]
         output: Option<PathBuf>,
 
         /// Play audio after synthesis
-        #[arg(short, long, default_value = "true")]
+        #[arg(short, long)]
         play: bool,
     },
+}
+
+/// Actions for the `config` subcommand
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Write a starter config file, optionally from a preset
+    Init {
+        /// Preset to start from, e.g. 'fast', 'slow', or 'whisper'
+        #[arg(long, default_value = "default")]
+        preset: String,
+
+        /// Overwrite an existing config file
+        #[arg(long)]
+        force: bool,
+    },
+    /// Print the loaded configuration as pretty JSON
+    Show,
+    /// Print a single configuration value
+    Get {
+        /// Field name, e.g. 'default_voice' or 'rate'
+        key: String,
+    },
+    /// Set a configuration value in the config file, with validation
+    Set {
+        /// Field name, e.g. 'default_voice' or 'rate'
+        key: String,
+
+        /// New value; coerced to the field's type
+        value: String,
+    },
+    /// Print the config file path in use (or where one would be created)
+    Path,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Convert text to speech
+    Speak(SpeakArgs),
     /// List available voices
     Voices {
         /// Filter by language code (e.g., 'en', 'fr', 'es')
@@ -40,135 +354,2684 @@ enum Commands {
         /// Show detailed information
         #[arg(short, long)]
         detailed: bool,
+
+        /// Export the catalog in a machine-readable format instead of
+        /// pretty console output
+        #[arg(short, long, value_enum)]
+        export: Option<ExportFormat>,
+
+        /// File to write the export to; stdout when omitted
+        #[arg(short, long, requires = "export")]
+        output: Option<PathBuf>,
+    },
+    /// Synthesize many items described by a manifest file
+    Batch {
+        /// Manifest path: CSV with a text,voice,output header, or a JSON
+        /// array of {text, voice, output} objects; voice and output are
+        /// optional per item
+        #[arg(short, long)]
+        manifest: PathBuf,
+
+        /// Write a caption file next to each audio output
+        #[arg(long, value_enum)]
+        subtitles: Option<SubtitleFormat>,
+
+        /// Output audio encoding; the file extension follows automatically
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+
+        /// List each job's resolved voice and target path without
+        /// synthesizing anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Skip items already recorded in the checkpoint file from an
+        /// interrupted run
+        #[arg(long)]
+        resume: bool,
+
+        /// How many syntheses to keep in flight; defaults to the
+        /// configured max_concurrent
+        #[arg(long, value_name = "N")]
+        parallel: Option<usize>,
+    },
+    /// Audition each voice for a language with a short sample
+    Preview {
+        /// Language whose voices to audition (e.g., 'fr')
+        #[arg(short, long)]
+        language: String,
+
+        /// Sample text; a short default phrase when omitted
+        #[arg(short, long)]
+        text: Option<String>,
+
+        /// How many voices to audition at most
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+    /// Synthesize the same sentence with several voices side by side
+    Compare {
+        /// Sentence to synthesize with every voice
+        #[arg(short, long)]
+        text: String,
+
+        /// Comma-separated voice names or aliases to compare
+        #[arg(long, value_delimiter = ',', required = true)]
+        voices: Vec<String>,
+
+        /// Directory the per-voice files land in
+        #[arg(long, default_value = "./compare")]
+        out: PathBuf,
+
+        /// Play the results back to back, announcing each voice first
+        #[arg(short, long)]
+        play: bool,
+    },
+    /// Play previously generated audio files in order
+    Play {
+        /// Audio files to play, queued back to back
+        #[arg(required_unless_present = "list_backends")]
+        files: Vec<PathBuf>,
+
+        /// Playback volume, 0.0 to 1.0
+        #[arg(long)]
+        volume: Option<f32>,
+
+        /// Playback speed, 1.0 = normal (also shifts pitch)
+        #[arg(long)]
+        speed: Option<f32>,
+
+        /// Audio backend to use (see --list-backends)
+        #[arg(short, long)]
+        backend: Option<String>,
+
+        /// List the audio backends compiled into this build and exit
+        #[arg(long, conflicts_with_all = ["files", "volume", "speed", "backend"])]
+        list_backends: bool,
+    },
+    /// Measure synthesis latency and throughput over repeated runs
+    Bench {
+        /// Voice to benchmark
+        #[arg(short, long, default_value = "en-US-AriaNeural")]
+        voice: String,
+
+        /// Text synthesized in every iteration
+        #[arg(
+            short,
+            long,
+            default_value = "The quick brown fox jumps over the lazy dog."
+        )]
+        text: String,
+
+        /// How many timed iterations to run
+        #[arg(short = 'n', long, default_value = "3")]
+        iterations: usize,
+    },
+    /// Review past synthesis jobs from the local history log
+    History {
+        /// How many of the most recent jobs to show
+        #[arg(short = 'n', long, default_value = "20")]
+        limit: usize,
+
+        /// Only show jobs whose voice contains this string
+        #[arg(long)]
+        voice: Option<String>,
+
+        /// Delete the history log instead of showing it
+        #[arg(long, conflicts_with_all = ["limit", "voice"])]
+        clear: bool,
     },
+    /// Show cumulative usage totals from the synthesis history
+    Stats,
+    /// Check the service, synthesis backends, audio device, and config
+    Doctor {
+        /// Also run a short synthesis round-trip, which touches the
+        /// network and takes a few seconds
+        #[arg(long)]
+        synthesize: bool,
+    },
+    /// Generate per-card audio for Anki-style flashcard decks
+    Anki {
+        /// CSV of cards with front,back columns (header optional)
+        cards: PathBuf,
+
+        /// Voice for the card fronts
+        #[arg(long, default_value = "en-US-AriaNeural")]
+        front_voice: String,
+
+        /// Voice for the card backs; the front voice when omitted
+        #[arg(long)]
+        back_voice: Option<String>,
+
+        /// Silence between front and back, in milliseconds
+        #[arg(long, default_value = "750")]
+        gap_ms: u64,
+
+        /// Directory the card audio and mapping file land in
+        #[arg(long, default_value = "./anki_audio")]
+        out: PathBuf,
+    },
+    /// Narrate the newest items of an RSS/Atom feed as podcast episodes
+    Rss {
+        /// Feed URL to turn into a podcast
+        feed: String,
+
+        /// Voice to narrate with
+        #[arg(short, long)]
+        voice: Option<String>,
+
+        /// Directory the episode audio and podcast.xml land in
+        #[arg(long, default_value = "./podcast")]
+        out: PathBuf,
+
+        /// How many of the newest items to synthesize
+        #[arg(long, default_value = "5")]
+        limit: usize,
+
+        /// Base URL for the enclosure links; plain file names when omitted
+        #[arg(long)]
+        base_url: Option<String>,
+    },
+    /// Narrate an EPUB or plain-text book chapter by chapter
+    Audiobook {
+        /// Book to narrate (.epub, .txt, or .md)
+        book: PathBuf,
+
+        /// Voice to narrate with
+        #[arg(short, long)]
+        voice: Option<String>,
+
+        /// Directory the chapter MP3s (or the M4B) land in
+        #[arg(long, default_value = "./audiobook")]
+        out: PathBuf,
+
+        /// Produce a single chaptered M4B instead of per-chapter MP3s
+        /// (requires ffmpeg on the PATH)
+        #[arg(long)]
+        m4b: bool,
+
+        /// Book title for the tags; defaults to the file name
+        #[arg(long)]
+        title: Option<String>,
+
+        /// Author for the tags
+        #[arg(long)]
+        author: Option<String>,
+    },
+    /// Monitor a directory and synthesize `.txt`/`.md` files as they change
+    Watch {
+        /// Directory to monitor for text files
+        dir: PathBuf,
+
+        /// Directory to write audio into, mirroring the input layout
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Voice to synthesize with
+        #[arg(short, long)]
+        voice: Option<String>,
+
+        /// Output audio encoding; the file extension follows automatically
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+    /// Expose /voices and /synthesize over HTTP for other local apps
+    Serve {
+        /// Port to listen on (bound to 127.0.0.1)
+        #[arg(short, long, default_value = "8080")]
+        port: u16,
+    },
+    /// Interactive mode: type lines to speak them immediately
+    Interactive {
+        /// Voice to start with
+        #[arg(short, long)]
+        voice: Option<String>,
+    },
+    /// Generate shell completions for bash, zsh, fish, or powershell
+    Completions {
+        /// Shell to generate completions for
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Use SSML prosody templates without writing SSML by hand
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
+    },
+    /// Create and edit the configuration file without hand-writing JSON
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Print the JSON Schema for the configuration file format
+    ConfigSchema,
     /// Run basic demo
     Demo {
         /// Language for demo
         #[arg(short, long, default_value = "en")]
         language: String,
+
+        /// How many syntheses to keep in flight; defaults to the
+        /// configured max_concurrent
+        #[arg(long, value_name = "N")]
+        parallel: Option<usize>,
+
+        /// Run once per language in the demo table instead of one language
+        #[arg(long, conflicts_with = "language")]
+        all_languages: bool,
+
+        /// JSON file overriding the embedded language/text/voice table
+        #[arg(long)]
+        config: Option<PathBuf>,
     },
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+/// Failure categories with stable exit codes, so calling scripts can
+/// branch on the failure type instead of parsing decorated output:
+/// 10 network, 11 voice not found, 12 invalid SSML, 13 audio device,
+/// 14 partial batch failure, 1 anything else. Network and audio device
+/// failures are recognized from the library error types in [`classify`].
+#[derive(Debug)]
+enum CliError {
+    VoiceNotFound(String),
+    InvalidSsml(Vec<String>),
+    PartialBatch { failed: usize, total: usize },
+}
+
+impl CliError {
+    fn exit_code(&self) -> i32 {
+        match self {
+            CliError::VoiceNotFound(_) => 11,
+            CliError::InvalidSsml(_) => 12,
+            CliError::PartialBatch { .. } => 14,
+        }
+    }
+
+    fn category(&self) -> &'static str {
+        match self {
+            CliError::VoiceNotFound(_) => "voice-not-found",
+            CliError::InvalidSsml(_) => "invalid-ssml",
+            CliError::PartialBatch { .. } => "partial-batch",
+        }
+    }
+}
+
+impl std::fmt::Display for CliError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CliError::VoiceNotFound(voice) => write!(
+                f,
+                "Voice '{}' not found; use 'hello-edge-tts voices' to list available voices",
+                voice
+            ),
+            CliError::InvalidSsml(problems) => {
+                write!(f, "Invalid SSML: {}", problems.join("; "))
+            }
+            CliError::PartialBatch { failed, total } => {
+                write!(f, "{} of {} batch job(s) failed", failed, total)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CliError {}
+
+/// Map library errors onto the CLI failure categories; everything
+/// unrecognized keeps the conventional exit code 1
+fn classify(error: &(dyn std::error::Error + 'static)) -> (i32, &'static str) {
+    if let Some(cli) = error.downcast_ref::<CliError>() {
+        return (cli.exit_code(), cli.category());
+    }
+    if let Some(tts) = error.downcast_ref::<TTSError>() {
+        return match tts {
+            TTSError::Network(_) => (10, "network"),
+            TTSError::VoiceNotFound(_) => (11, "voice-not-found"),
+            _ => (1, "error"),
+        };
+    }
+    if let Some(AudioError::Device(_)) = error.downcast_ref::<AudioError>() {
+        return (13, "audio-device");
+    }
+    (1, "error")
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    // Route library logs through tracing on stderr; RUST_LOG still wins
+    // when set so debugging sessions can filter by module
+    let level = if cli.quiet {
+        "error"
+    } else {
+        match cli.verbose {
+            0 => "warn",
+            1 => "info",
+            _ => "debug",
+        }
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new(level)),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let json = cli.json;
+    if let Err(error) = run(cli).await {
+        let (code, category) = classify(error.as_ref());
+        // Batch already printed its JSON summary, failures included
+        let already_reported = json
+            && matches!(
+                error.downcast_ref::<CliError>(),
+                Some(CliError::PartialBatch { .. })
+            );
+        if json && !already_reported {
+            let problems = match error.downcast_ref::<CliError>() {
+                Some(CliError::InvalidSsml(problems)) => Some(problems.clone()),
+                _ => None,
+            };
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "error",
+                    "category": category,
+                    "error": error.to_string(),
+                    "problems": problems,
+                })
+            );
+        } else if !json {
+            eprintln!("❌ {}", error);
+        }
+        std::process::exit(code);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), Box<dyn std::error::Error>> {
+    match cli.command {
+        Commands::Speak(args) => {
+            handle_speak(args, cli.json).await?;
+        }
+        Commands::Voices {
+            language,
+            detailed,
+            export,
+            output,
+        } => {
+            // --json is shorthand for a JSON export to stdout
+            let export = export.or(cli.json.then_some(ExportFormat::Json));
+            handle_voices(language, detailed, export, output).await?;
+        }
+        Commands::Batch {
+            manifest,
+            subtitles,
+            format,
+            dry_run,
+            resume,
+            parallel,
+        } => {
+            handle_batch(manifest, subtitles, format, dry_run, resume, parallel, cli.json).await?;
+        }
+        Commands::Preview {
+            language,
+            text,
+            limit,
+        } => {
+            handle_preview(language, text, limit).await?;
+        }
+        Commands::Compare {
+            text,
+            voices,
+            out,
+            play,
+        } => {
+            handle_compare(text, voices, out, play, cli.json).await?;
+        }
+        Commands::Play {
+            files,
+            volume,
+            speed,
+            backend,
+            list_backends,
+        } => {
+            if list_backends {
+                for name in AudioPlayer::available_backends() {
+                    println!("{}", name);
+                }
+            } else {
+                handle_play(files, volume, speed, backend)?;
+            }
+        }
+        Commands::Bench {
+            voice,
+            text,
+            iterations,
+        } => {
+            handle_bench(voice, text, iterations, cli.json).await?;
+        }
+        Commands::History {
+            limit,
+            voice,
+            clear,
+        } => {
+            handle_history(limit, voice, clear, cli.json).await?;
+        }
+        Commands::Stats => {
+            handle_stats(cli.json).await?;
+        }
+        Commands::Doctor { synthesize } => {
+            handle_doctor(synthesize, cli.json).await?;
+        }
+        Commands::Anki {
+            cards,
+            front_voice,
+            back_voice,
+            gap_ms,
+            out,
+        } => {
+            handle_anki(cards, front_voice, back_voice, gap_ms, out, cli.json).await?;
+        }
+        Commands::Rss {
+            feed,
+            voice,
+            out,
+            limit,
+            base_url,
+        } => {
+            handle_rss(feed, voice, out, limit, base_url, cli.json).await?;
+        }
+        Commands::Audiobook {
+            book,
+            voice,
+            out,
+            m4b,
+            title,
+            author,
+        } => {
+            handle_audiobook(book, voice, out, m4b, title, author, cli.json).await?;
+        }
+        Commands::Watch {
+            dir,
+            out,
+            voice,
+            format,
+        } => {
+            handle_watch(dir, out, voice, format).await?;
+        }
+        Commands::Serve { port } => {
+            handle_serve(port).await?;
+        }
+        Commands::Interactive { voice } => {
+            handle_interactive(voice).await?;
+        }
+        Commands::Completions { shell } => {
+            use clap::CommandFactory;
+
+            let mut command = Cli::command();
+            let name = command.get_name().to_string();
+            clap_complete::generate(shell, &mut command, name, &mut std::io::stdout());
+
+            // Complete --voice values dynamically from the live catalog
+            if matches!(shell, clap_complete::Shell::Bash | clap_complete::Shell::Zsh) {
+                println!();
+                println!("_hello_edge_tts_voice_names() {{");
+                println!(
+                    "    hello-edge-tts voices --export csv 2>/dev/null | tail -n +2 | cut -d, -f1"
+                );
+                println!("}}");
+            }
+        }
+        Commands::Template { action } => {
+            handle_template(action, cli.json).await?;
+        }
+        Commands::Config { action } => {
+            handle_config(action, cli.json)?;
+        }
+        Commands::ConfigSchema => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&TTSConfig::json_schema())?
+            );
+        }
+        Commands::Demo {
+            language,
+            parallel,
+            all_languages,
+            config,
+        } => {
+            handle_demo(language, parallel, all_languages, config).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn handle_speak(args: SpeakArgs, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let SpeakArgs {
+        text,
+        file,
+        clipboard,
+        voice,
+        ssml,
+        subtitles,
+        rate,
+        pitch,
+        volume,
+        format,
+        output,
+        play,
+        stream,
+        dry_run,
+    } = args;
+
+    let (text, long_input) = if clipboard {
+        let mut clipboard = arboard::Clipboard::new()
+            .map_err(|e| format!("Cannot access the clipboard: {}", e))?;
+        let text = clipboard
+            .get_text()
+            .map_err(|e| format!("Clipboard holds no text: {}", e))?;
+        (text, true)
+    } else {
+        match file {
+            Some(path) => (std::fs::read_to_string(&path)?, true),
+            None => match text.expect("clap enforces --text, --file, or --clipboard") {
+                // '-' means read the text from stdin, e.g. from a pipe
+                text if text == "-" => {
+                    use std::io::Read;
+                    let mut buffer = String::new();
+                    std::io::stdin().read_to_string(&mut buffer)?;
+                    (buffer, true)
+                }
+                text => (text, false),
+            },
+        }
+    };
+    let long_input = long_input && !ssml;
+
+    // With '--output -' the audio goes to stdout, so all status chatter
+    // moves to stderr to keep the pipe clean; with --json, decorated text
+    // is suppressed and a single JSON object replaces it
+    let to_stdout = output.as_deref() == Some(std::path::Path::new("-"));
+    macro_rules! status {
+        ($($arg:tt)*) => {
+            if json {
+            } else if to_stdout {
+                eprintln!($($arg)*);
+            } else {
+                println!($($arg)*);
+            }
+        };
+    }
+    // The JSON result rides stderr when stdout carries the audio itself
+    let emit = |value: serde_json::Value| {
+        if to_stdout {
+            eprintln!("{}", value);
+        } else {
+            println!("{}", value);
+        }
+    };
+
+    status!("🎤 Converting text to speech...");
+    let preview: String = text.chars().take(80).collect();
+    status!("Text: {}", preview);
+
+    if ssml {
+        let problems = SSMLValidator::validate(&text);
+        if !problems.is_empty() {
+            return Err(CliError::InvalidSsml(problems).into());
+        }
+    }
+
+    let mut config = load_config(None).unwrap_or_default();
+    if let Some(rate) = rate {
+        config.rate = rate;
+    }
+    if let Some(pitch) = pitch {
+        config.pitch = pitch;
+    }
+    if let Some(volume) = volume {
+        config.volume = volume;
+    }
+    if let Some(format) = format {
+        config.output_format = format.as_str().to_string();
+    }
+    config.validate()?;
+
+    let voice = config.resolve_voice(&voice);
+    status!("Voice: {}", voice);
+
+    if dry_run {
+        // Show what would be sent and written, then stop before anything
+        // touches the network
+        let ssml_payload = if ssml {
+            text.clone()
+        } else {
+            let overridden = |value: &str, default: &str| value != default;
+            hello_edge_tts::ssml_utils::create_ssml(
+                &text,
+                &voice,
+                overridden(&config.rate, "0%").then_some(config.rate.as_str()),
+                overridden(&config.pitch, "0%").then_some(config.pitch.as_str()),
+                overridden(&config.volume, "100%").then_some(config.volume.as_str()),
+            )
+        };
+        let output_path = if to_stdout {
+            PathBuf::from("-")
+        } else {
+            output.unwrap_or_else(|| {
+                let lang = voice.split('-').next().unwrap_or("unknown");
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                config.resolve_output_path(&format!("edgetts_{}_rust_{}", lang, timestamp))
+            })
+        };
+        let subtitle_path = subtitles
+            .filter(|_| !to_stdout)
+            .map(|format| output_path.with_extension(format.extension()));
+
+        if json {
+            emit(serde_json::json!({
+                "status": "dry-run",
+                "voice": voice,
+                "format": config.output_format,
+                "output": output_path,
+                "subtitles": subtitle_path,
+                "ssml": ssml_payload,
+            }));
+        } else {
+            status!("🧪 Dry run — nothing synthesized");
+            status!("Format: {}", config.output_format);
+            status!("Output: {}", output_path.display());
+            if let Some(path) = subtitle_path {
+                status!("Subtitles: {}", path.display());
+            }
+            status!("SSML:");
+            status!("{}", pretty_print_ssml(&ssml_payload).trim_end());
+        }
+        return Ok(());
+    }
+
+    let mut client = TTSClient::new(Some(config.clone()));
+
+    // Verify the voice exists
+    let voices = client.list_voices().await?;
+    if !voices.iter().any(|v| v.name == voice) {
+        return Err(CliError::VoiceNotFound(voice).into());
+    }
+
+    if stream {
+        use futures_util::StreamExt;
+        use std::sync::{Arc, Mutex};
+
+        let player = AudioPlayer::new()?;
+        if player.is_null() {
+            eprintln!("⚠️  No audio device available — streaming playback is silent");
+        }
+        status!("🔊 Streaming — playback starts with the first chunk...");
+
+        // play_stream wants a plain byte stream, so a failed chunk parks
+        // its error here and ends the stream; the error wins over the
+        // decoder complaint the truncation provokes
+        let failure: Arc<Mutex<Option<hello_edge_tts::TTSError>>> = Arc::new(Mutex::new(None));
+        let sink = Arc::clone(&failure);
+        let audio = client.synthesize_stream(&text, &voice).filter_map(move |item| {
+            let sink = Arc::clone(&sink);
+            async move {
+                match item {
+                    Ok(chunk) => Some(chunk),
+                    Err(e) => {
+                        *sink.lock().unwrap() = Some(e);
+                        None
+                    }
+                }
+            }
+        });
+
+        let playback = player.play_stream(audio).await;
+        if let Some(e) = failure.lock().unwrap().take() {
+            return Err(e.into());
+        }
+        playback?;
+        status!("✅ Playback finished");
+        if json {
+            emit(serde_json::json!({
+                "status": "ok",
+                "voice": voice,
+                "streamed": true,
+            }));
+        }
+        return Ok(());
+    }
+
+    let synthesis = if ssml {
+        client.synthesize_ssml(&text, &voice).await
+    } else if long_input {
+        let reporter = BarReporter::new("Synthesizing");
+        let result = client
+            .synthesize_long_text_with_progress(&text, &voice, Some(&reporter))
+            .await;
+        reporter.finish();
+        result
+    } else {
+        client.synthesize_text(&text, &voice, None).await
+    };
+    match synthesis {
+        Ok(audio_data) if to_stdout => {
+            use std::io::Write;
+            let mut stdout = std::io::stdout().lock();
+            stdout.write_all(&audio_data)?;
+            stdout.flush()?;
+            record_history(&voice, &text, &audio_data, "-");
+            if json {
+                emit(serde_json::json!({
+                    "status": "ok",
+                    "voice": voice,
+                    "bytes": audio_data.len(),
+                    "output": "-",
+                }));
+            } else {
+                status!("✅ Wrote {} bytes to stdout", audio_data.len());
+            }
+        }
+        Ok(audio_data) => {
+            let output_path = output.unwrap_or_else(|| {
+                // Extract language from voice (e.g., 'en' from 'en-US-AriaNeural')
+                let lang = voice.split('-').next().unwrap_or("unknown");
+                let timestamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs();
+                // Lands in output_directory with the configured format's extension
+                config.resolve_output_path(&format!("edgetts_{}_rust_{}", lang, timestamp))
+            });
+
+            client
+                .save_audio(&audio_data, output_path.to_str().unwrap())
+                .await?;
+            record_history(&voice, &text, &audio_data, &output_path.display().to_string());
+            status!("✅ Audio saved to: {}", output_path.display());
+
+            let mut subtitle_path = None;
+            if let Some(format) = subtitles {
+                // Captions always come from the plain words
+                let caption_text = if ssml {
+                    hello_edge_tts::ssml_utils::strip_ssml(&text)
+                } else {
+                    text.clone()
+                };
+                match write_subtitles(format, &caption_text, &output_path) {
+                    Ok(path) => {
+                        status!("✅ Subtitles saved to: {}", path.display());
+                        subtitle_path = Some(path);
+                    }
+                    Err(e) => eprintln!("❌ Failed to write subtitles: {}", e),
+                }
+            }
+
+            if json {
+                emit(serde_json::json!({
+                    "status": "ok",
+                    "voice": voice,
+                    "bytes": audio_data.len(),
+                    "output": output_path,
+                    "subtitles": subtitle_path,
+                }));
+            }
+
+            if play {
+                status!("🔊 Playing audio...");
+                match AudioPlayer::new() {
+                    Ok(player) => {
+                        if let Err(e) = player.play_file(output_path.to_str().unwrap()) {
+                            eprintln!("❌ Failed to play audio: {}", e);
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("❌ Failed to create audio player: {}", e);
+                    }
+                }
+            }
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// One synthesis job from a batch manifest; voice and output fall back to
+/// the configured defaults when omitted
+#[derive(serde::Deserialize)]
+struct BatchJob {
+    text: String,
+    #[serde(default)]
+    voice: Option<String>,
+    #[serde(default)]
+    output: Option<String>,
+}
+
+fn parse_manifest(path: &std::path::Path) -> Result<Vec<BatchJob>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    if path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json")) {
+        return Ok(serde_json::from_str(&content)?);
+    }
+
+    // CSV: a text,voice,output header followed by one row per job
+    let mut lines = content.lines().filter(|l| !l.trim().is_empty());
+    let header: Vec<String> = parse_csv_row(lines.next().ok_or("Manifest is empty")?);
+    let column = |name: &str| header.iter().position(|h| h.trim() == name);
+    let text_col = column("text").ok_or("Manifest CSV needs a 'text' column")?;
+    let voice_col = column("voice");
+    let output_col = column("output");
+
+    let mut jobs = Vec::new();
+    for line in lines {
+        let row = parse_csv_row(line);
+        let cell = |col: Option<usize>| {
+            col.and_then(|i| row.get(i))
+                .filter(|v| !v.is_empty())
+                .cloned()
+        };
+        jobs.push(BatchJob {
+            text: cell(Some(text_col)).ok_or("Manifest row is missing its text")?,
+            voice: cell(voice_col),
+            output: cell(output_col),
+        });
+    }
+    Ok(jobs)
+}
+
+/// Split one CSV row into fields, honoring double-quoted fields with ""
+/// escapes — enough for manifests without pulling in a CSV crate
+fn parse_csv_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+async fn handle_batch(
+    manifest: PathBuf,
+    subtitles: Option<SubtitleFormat>,
+    format: Option<OutputFormat>,
+    dry_run: bool,
+    resume: bool,
+    parallel: Option<usize>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let jobs = parse_manifest(&manifest)?;
+    if jobs.is_empty() {
+        eprintln!("Manifest contains no jobs.");
+        return Ok(());
+    }
+    if !json && !dry_run {
+        println!("📦 Running {} batch job(s) from {}", jobs.len(), manifest.display());
+    }
+
+    let mut config = load_config(None).unwrap_or_default();
+    if let Some(format) = format {
+        config.output_format = format.as_str().to_string();
+    }
+
+    if dry_run {
+        // Resolve every job's voice and target path without synthesizing
+        let resolved: Vec<_> = jobs
+            .iter()
+            .enumerate()
+            .map(|(i, job)| {
+                let voice =
+                    config.resolve_voice(job.voice.as_deref().unwrap_or(&config.default_voice));
+                let output = job
+                    .output
+                    .clone()
+                    .unwrap_or_else(|| format!("batch_{:03}", i + 1));
+                let audio_path = config.resolve_output_path(&output);
+                let subtitle_path =
+                    subtitles.map(|format| audio_path.with_extension(format.extension()));
+                (voice, audio_path, subtitle_path)
+            })
+            .collect();
+
+        if json {
+            println!(
+                "{}",
+                serde_json::json!({
+                    "status": "dry-run",
+                    "format": config.output_format,
+                    "jobs": resolved
+                        .iter()
+                        .map(|(voice, audio_path, subtitle_path)| serde_json::json!({
+                            "voice": voice,
+                            "output": audio_path,
+                            "subtitles": subtitle_path,
+                        }))
+                        .collect::<Vec<_>>(),
+                })
+            );
+        } else {
+            println!(
+                "🧪 Dry run — {} job(s), format {}, nothing synthesized",
+                jobs.len(),
+                config.output_format
+            );
+            for (i, (voice, audio_path, subtitle_path)) in resolved.iter().enumerate() {
+                match subtitle_path {
+                    Some(path) => println!(
+                        "   {:>3}. {} -> {} (+ {})",
+                        i + 1,
+                        voice,
+                        audio_path.display(),
+                        path.display()
+                    ),
+                    None => println!("   {:>3}. {} -> {}", i + 1, voice, audio_path.display()),
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    let client = TTSClient::new(Some(config.clone()));
+
+    // Completed items land in a checkpoint next to the manifest, keyed by
+    // output name with a digest of everything that affects the audio, so
+    // an interrupted run restarted with --resume skips what already exists
+    let checkpoint_path = manifest.with_extension("checkpoint.json");
+    let mut checkpoint: std::collections::HashMap<String, String> = if resume {
+        std::fs::read_to_string(&checkpoint_path)
+            .ok()
+            .and_then(|s| serde_json::from_str(&s).ok())
+            .unwrap_or_default()
+    } else {
+        std::collections::HashMap::new()
+    };
+
+    // Resolve everything up front so skipped items never enter the queue
+    struct ResolvedJob {
+        item: usize,
+        text: String,
+        voice: String,
+        output: String,
+        digest: String,
+    }
+
+    let mut skipped = 0usize;
+    let mut pending = Vec::new();
+    for (i, job) in jobs.iter().enumerate() {
+        let voice = config.resolve_voice(job.voice.as_deref().unwrap_or(&config.default_voice));
+        let output = job
+            .output
+            .clone()
+            .unwrap_or_else(|| format!("batch_{:03}", i + 1));
+
+        let digest = content_digest(&[&job.text, &voice, &output, &config.output_format]);
+        if resume
+            && checkpoint.get(&output) == Some(&digest)
+            && config.resolve_output_path(&output).exists()
+        {
+            skipped += 1;
+            continue;
+        }
+        pending.push(ResolvedJob {
+            item: i + 1,
+            text: job.text.clone(),
+            voice,
+            output,
+            digest,
+        });
+    }
+
+    // The flag overrides the configured limit; either way at least one
+    // synthesis is always in flight
+    let parallel = parallel.unwrap_or(config.max_concurrent).max(1);
+
+    let mut failures: Vec<(usize, String, String)> = Vec::new();
+    let reporter = BarReporter::new("Batch");
+    let mut total_bytes = 0u64;
+    let mut done = skipped;
+    reporter.on_progress(done, jobs.len(), total_bytes);
+
+    {
+        use futures_util::stream::StreamExt;
+
+        let client = &client;
+        let mut stream = futures_util::stream::iter(pending.into_iter().map(|job| async move {
+            let result = match client.synthesize_long_text(&job.text, &job.voice).await {
+                Ok(audio_data) => {
+                    let bytes = audio_data.len() as u64;
+                    let saved = client.save_audio(&audio_data, &job.output).await;
+                    if saved.is_ok() {
+                        record_history(&job.voice, &job.text, &audio_data, &job.output);
+                    }
+                    saved.map(|()| bytes)
+                }
+                Err(e) => Err(e),
+            };
+            (job, result)
+        }))
+        .buffer_unordered(parallel);
+
+        while let Some((job, result)) = stream.next().await {
+            done += 1;
+            match result {
+                Ok(bytes) => {
+                    total_bytes += bytes;
+                    if let Some(format) = subtitles {
+                        let audio_path = config.resolve_output_path(&job.output);
+                        if let Err(e) = write_subtitles(format, &job.text, &audio_path) {
+                            eprintln!("   ❌ Failed to write subtitles: {}", e);
+                        }
+                    }
+                    // Persist after every item so an interruption loses nothing
+                    checkpoint.insert(job.output, job.digest);
+                    if let Ok(rendered) = serde_json::to_string_pretty(&checkpoint) {
+                        let _ = std::fs::write(&checkpoint_path, rendered);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("   ❌ {}", e);
+                    failures.push((job.item, job.output, e.to_string()));
+                }
+            }
+            reporter.on_progress(done, jobs.len(), total_bytes);
+        }
+    }
+    // Completion order is nondeterministic; report failures by item
+    failures.sort_by_key(|(item, _, _)| *item);
+
+    reporter.finish();
+    // A fully successful run needs no checkpoint to come back to
+    if failures.is_empty() {
+        let _ = std::fs::remove_file(&checkpoint_path);
+    }
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if failures.is_empty() { "ok" } else { "error" },
+                "category": (!failures.is_empty()).then_some("partial-batch"),
+                "total": jobs.len(),
+                "succeeded": jobs.len() - failures.len() - skipped,
+                "skipped": skipped,
+                "failed": failures.len(),
+                "failures": failures
+                    .iter()
+                    .map(|(item, output, error)| serde_json::json!({
+                        "item": item,
+                        "output": output,
+                        "error": error,
+                    }))
+                    .collect::<Vec<_>>(),
+            })
+        );
+    } else {
+        println!("{}", "=".repeat(40));
+        println!(
+            "📊 Batch complete: {} succeeded, {} skipped, {} failed",
+            jobs.len() - failures.len() - skipped,
+            skipped,
+            failures.len()
+        );
+        for (item, output, error) in &failures {
+            println!("   ❌ item {} ({}): {}", item, output, error);
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(CliError::PartialBatch {
+            failed: failures.len(),
+            total: jobs.len(),
+        }
+        .into())
+    }
+}
+
+/// Turn a chapter or book title into a safe file name fragment
+fn slugify(title: &str) -> String {
+    let mut slug = String::new();
+    for c in title.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('_') && !slug.is_empty() {
+            slug.push('_');
+        }
+    }
+    let slug = slug.trim_end_matches('_');
+    if slug.is_empty() {
+        "chapter".to_string()
+    } else {
+        slug.chars().take(40).collect()
+    }
+}
+
+/// Split a plain-text or Markdown book into chapters at heading lines
+/// (`# Title`, `Chapter 1`, …); books without headings become one chapter
+fn read_text_chapters(path: &std::path::Path) -> std::io::Result<Vec<(String, String)>> {
+    let content = std::fs::read_to_string(path)?;
+    let heading =
+        regex::Regex::new(r"(?mi)^\s*(#{1,3}\s+\S.*|chapter\s+[\divxlc]+\b.*)\s*$").unwrap();
+
+    let fallback_title = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Book".to_string());
+
+    let matches: Vec<_> = heading.find_iter(&content).collect();
+    if matches.is_empty() {
+        return Ok(vec![(fallback_title, content)]);
+    }
+
+    let mut chapters = Vec::new();
+    let preamble = content[..matches[0].start()].trim();
+    if !preamble.is_empty() {
+        chapters.push(("Introduction".to_string(), preamble.to_string()));
+    }
+    for (i, m) in matches.iter().enumerate() {
+        let title = m.as_str().trim().trim_start_matches('#').trim().to_string();
+        let end = matches
+            .get(i + 1)
+            .map(|next| next.start())
+            .unwrap_or(content.len());
+        let body = content[m.end()..end].trim();
+        if !body.is_empty() {
+            chapters.push((title, body.to_string()));
+        }
+    }
+    Ok(chapters)
+}
+
+/// Extract chapters from an EPUB in spine order: resolve the OPF through
+/// META-INF/container.xml, then strip each spine document down to its
+/// speakable text. Front matter without prose (covers, tables of contents)
+/// is dropped.
+fn read_epub_chapters(
+    path: &std::path::Path,
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)?;
+
+    let read_entry = |archive: &mut zip::ZipArchive<std::fs::File>,
+                      name: &str|
+     -> Result<String, Box<dyn std::error::Error>> {
+        use std::io::Read;
+        let mut entry = archive
+            .by_name(name)
+            .map_err(|_| format!("EPUB is missing entry '{}'", name))?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content)?;
+        Ok(content)
+    };
+
+    let container = read_entry(&mut archive, "META-INF/container.xml")?;
+    let opf_path = regex::Regex::new(r#"full-path="([^"]+)""#)
+        .unwrap()
+        .captures(&container)
+        .map(|c| c[1].to_string())
+        .ok_or("EPUB container.xml names no OPF package")?;
+    let opf_dir = match opf_path.rfind('/') {
+        Some(i) => &opf_path[..=i],
+        None => "",
+    };
+    let opf = read_entry(&mut archive, &opf_path)?;
+
+    // Manifest maps ids to files; the spine gives the reading order
+    let mut hrefs = std::collections::HashMap::new();
+    let item_re = regex::Regex::new(r"<item\b[^>]*>").unwrap();
+    let attr = |tag: &str, name: &str| -> Option<String> {
+        regex::Regex::new(&format!(r#"{}="([^"]+)""#, name))
+            .unwrap()
+            .captures(tag)
+            .map(|c| c[1].to_string())
+    };
+    for tag in item_re.find_iter(&opf) {
+        if let (Some(id), Some(href)) = (attr(tag.as_str(), "id"), attr(tag.as_str(), "href")) {
+            hrefs.insert(id, href);
+        }
+    }
+
+    let title_re = regex::Regex::new(r"(?s)<(?:title|h1)[^>]*>(.*?)</(?:title|h1)>").unwrap();
+    let mut chapters = Vec::new();
+    let idref_re = regex::Regex::new(r#"<itemref\b[^>]*idref="([^"]+)""#).unwrap();
+    let idrefs: Vec<String> = idref_re
+        .captures_iter(&opf)
+        .map(|c| c[1].to_string())
+        .collect();
+    for idref in idrefs {
+        let Some(href) = hrefs.get(&idref) else {
+            continue;
+        };
+        let Ok(document) = read_entry(&mut archive, &format!("{}{}", opf_dir, href)) else {
+            continue;
+        };
+        let text = hello_edge_tts::ssml_utils::strip_ssml(&document);
+        if text.split_whitespace().count() < 10 {
+            continue;
+        }
+        let title = title_re
+            .captures(&document)
+            .map(|c| hello_edge_tts::ssml_utils::strip_ssml(&c[1]))
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| format!("Chapter {}", chapters.len() + 1));
+        chapters.push((title, text));
+    }
+    Ok(chapters)
+}
+
+async fn handle_anki(
+    cards: PathBuf,
+    front_voice: String,
+    back_voice: Option<String>,
+    gap_ms: u64,
+    out: PathBuf,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(&cards)?;
+    let mut rows: Vec<(String, String)> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let fields = parse_csv_row(line);
+            let front = fields.first().cloned().unwrap_or_default();
+            let back = fields.get(1).cloned().unwrap_or_default();
+            (front, back)
+        })
+        .collect();
+    // A leading front,back header is metadata, not a card
+    if rows
+        .first()
+        .is_some_and(|(front, _)| front.trim().eq_ignore_ascii_case("front"))
+    {
+        rows.remove(0);
+    }
+    if rows.is_empty() {
+        return Err(format!("No cards found in {}", cards.display()).into());
+    }
+
+    let config = load_config(None).unwrap_or_default();
+    let front_voice = config.resolve_voice(&front_voice);
+    let back_voice = config.resolve_voice(back_voice.as_deref().unwrap_or(&front_voice));
+    let gap = std::time::Duration::from_millis(gap_ms);
+    std::fs::create_dir_all(&out)?;
+
+    if !json {
+        println!(
+            "🃏 Generating audio for {} card(s) ({} / {})",
+            rows.len(),
+            front_voice,
+            back_voice
+        );
+    }
+
+    let client = TTSClient::new(Some(config.clone()));
+    let reporter = BarReporter::new("Cards");
+    let mut mapping = String::new();
+    let mut outputs = Vec::new();
+    let mut total_bytes = 0u64;
+    for (i, (front, back)) in rows.iter().enumerate() {
+        let front_audio = client.synthesize_text(front, &front_voice, None).await?;
+        let combined = if back.trim().is_empty() {
+            front_audio
+        } else {
+            let back_audio = client.synthesize_text(back, &back_voice, None).await?;
+            hello_edge_tts::audio_processing::concat_data_with_silence(
+                &[front_audio, back_audio],
+                gap,
+            )?
+        };
+        total_bytes += combined.len() as u64;
+
+        let file_name = format!("card_{:03}.{}", i + 1, config.output_format);
+        let path = out.join(&file_name);
+        client.save_audio(&combined, path.to_str().unwrap()).await?;
+        record_history(
+            &front_voice,
+            &format!("{} {}", front, back),
+            &combined,
+            &path.display().to_string(),
+        );
+
+        // Anki's plain-text importer takes front, back, then a sound tag
+        mapping.push_str(&format!("{}\t{}\t[sound:{}]\n", front, back, file_name));
+        outputs.push(path);
+        reporter.on_progress(i + 1, rows.len(), total_bytes);
+    }
+    reporter.finish();
+
+    let mapping_path = out.join("cards.tsv");
+    std::fs::write(&mapping_path, mapping)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "cards": rows.len(),
+                "front_voice": front_voice,
+                "back_voice": back_voice,
+                "outputs": outputs,
+                "mapping": mapping_path,
+            })
+        );
+    } else {
+        println!(
+            "🎉 {} card(s) written; import {} into Anki",
+            rows.len(),
+            mapping_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Escape the five XML special characters for feed output
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// One feed item worth narrating
+struct FeedItem {
+    title: String,
+    link: Option<String>,
+    text: String,
+}
+
+/// Pull the readable items out of an RSS or Atom document. Content comes
+/// from `content:encoded`, `description`, or Atom `content`, CDATA
+/// unwrapped and markup stripped.
+fn parse_feed(document: &str) -> (String, Vec<FeedItem>) {
+    let unwrap_cdata = |s: &str| -> String {
+        let trimmed = s.trim();
+        trimmed
+            .strip_prefix("<![CDATA[")
+            .and_then(|rest| rest.strip_suffix("]]>"))
+            .unwrap_or(trimmed)
+            .to_string()
+    };
+    let field = |block: &str, tag: &str| -> Option<String> {
+        regex::Regex::new(&format!(r"(?s)<{0}[^>]*>(.*?)</{0}>", tag))
+            .unwrap()
+            .captures(block)
+            .map(|c| unwrap_cdata(&c[1]))
+    };
+    let readable = |block: &str| -> String {
+        ["content:encoded", "description", "content", "summary"]
+            .iter()
+            .find_map(|tag| field(block, tag))
+            .map(|html| hello_edge_tts::ssml_utils::strip_ssml(&html))
+            .unwrap_or_default()
+    };
+
+    let item_re = regex::Regex::new(r"(?s)<(?:item|entry)[\s>](.*?)</(?:item|entry)>").unwrap();
+    let items = item_re
+        .captures_iter(document)
+        .map(|c| {
+            let block = &c[1];
+            FeedItem {
+                title: field(block, "title")
+                    .map(|t| hello_edge_tts::ssml_utils::strip_ssml(&t))
+                    .filter(|t| !t.is_empty())
+                    .unwrap_or_else(|| "Untitled".to_string()),
+                link: field(block, "link").filter(|l| !l.is_empty()),
+                text: readable(block),
+            }
+        })
+        .collect();
+
+    // The channel title precedes the first item; strip items first so an
+    // item's <title> can't shadow it
+    let channel_title = item_re
+        .splitn(document, 2)
+        .next()
+        .and_then(|head| field(head, "title"))
+        .map(|t| hello_edge_tts::ssml_utils::strip_ssml(&t))
+        .filter(|t| !t.is_empty())
+        .unwrap_or_else(|| "Narrated feed".to_string());
+
+    (channel_title, items)
+}
+
+async fn handle_rss(
+    feed: String,
+    voice: Option<String>,
+    out: PathBuf,
+    limit: usize,
+    base_url: Option<String>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !json {
+        println!("📡 Fetching feed {}", feed);
+    }
+    let document = reqwest::get(&feed).await?.error_for_status()?.text().await?;
+    let (channel_title, items) = parse_feed(&document);
+    let items: Vec<_> = items
+        .into_iter()
+        .filter(|item| !item.text.trim().is_empty())
+        .take(limit)
+        .collect();
+    if items.is_empty() {
+        return Err("Feed contains no items with readable text".into());
+    }
+
+    let config = load_config(None).unwrap_or_default();
+    let voice = config.resolve_voice(voice.as_deref().unwrap_or(&config.default_voice));
+    std::fs::create_dir_all(&out)?;
+
+    if !json {
+        println!(
+            "🎙️  Narrating {} episode(s) of '{}' with {}",
+            items.len(),
+            channel_title,
+            voice
+        );
+    }
+
+    let client = TTSClient::new(Some(config.clone()));
+    let reporter = BarReporter::new("Episodes");
+    let mut episodes: Vec<(String, PathBuf, u64)> = Vec::new();
+    let mut total_bytes = 0u64;
+    for (i, item) in items.iter().enumerate() {
+        let audio_data = client.synthesize_long_text(&item.text, &voice).await?;
+        total_bytes += audio_data.len() as u64;
+        let path = out.join(format!("episode_{:02}_{}.mp3", i + 1, slugify(&item.title)));
+        client
+            .save_audio_with_tags(
+                &audio_data,
+                path.to_str().unwrap(),
+                &hello_edge_tts::AudioTags {
+                    title: Some(item.title.clone()),
+                    artist: None,
+                    album: Some(channel_title.clone()),
+                    track: Some(i as u32 + 1),
+                    language: None,
+                    lyrics: None,
+                },
+            )
+            .await?;
+        record_history(&voice, &item.text, &audio_data, &path.display().to_string());
+        let bytes = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+        episodes.push((item.title.clone(), path, bytes));
+        reporter.on_progress(i + 1, items.len(), total_bytes);
+    }
+    reporter.finish();
+
+    // Write the podcast feed next to the audio, enclosures included
+    let now = chrono::Utc::now().to_rfc2822();
+    let mut rss = String::new();
+    rss.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    rss.push_str("<rss version=\"2.0\">\n<channel>\n");
+    rss.push_str(&format!(
+        "  <title>{} (narrated)</title>\n  <link>{}</link>\n  <description>Narrated by hello-edge-tts with {}</description>\n",
+        xml_escape(&channel_title),
+        xml_escape(&feed),
+        xml_escape(&voice)
+    ));
+    for ((title, path, bytes), item) in episodes.iter().zip(&items) {
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let enclosure_url = match &base_url {
+            Some(base) => format!("{}/{}", base.trim_end_matches('/'), file_name),
+            None => file_name.clone(),
+        };
+        rss.push_str("  <item>\n");
+        rss.push_str(&format!("    <title>{}</title>\n", xml_escape(title)));
+        if let Some(link) = &item.link {
+            rss.push_str(&format!("    <link>{}</link>\n", xml_escape(link)));
+        }
+        rss.push_str(&format!(
+            "    <enclosure url=\"{}\" length=\"{}\" type=\"audio/mpeg\"/>\n",
+            xml_escape(&enclosure_url),
+            bytes
+        ));
+        rss.push_str(&format!(
+            "    <guid isPermaLink=\"false\">{}</guid>\n    <pubDate>{}</pubDate>\n",
+            xml_escape(&file_name),
+            now
+        ));
+        rss.push_str("  </item>\n");
+    }
+    rss.push_str("</channel>\n</rss>\n");
+    let feed_path = out.join("podcast.xml");
+    std::fs::write(&feed_path, rss)?;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "channel": channel_title,
+                "voice": voice,
+                "episodes": episodes
+                    .iter()
+                    .map(|(title, path, bytes)| serde_json::json!({
+                        "title": title,
+                        "output": path,
+                        "bytes": bytes,
+                    }))
+                    .collect::<Vec<_>>(),
+                "feed": feed_path,
+            })
+        );
+    } else {
+        println!("🎉 Podcast written to {}", feed_path.display());
+    }
+    Ok(())
+}
+
+async fn handle_audiobook(
+    book: PathBuf,
+    voice: Option<String>,
+    out: PathBuf,
+    m4b: bool,
+    title: Option<String>,
+    author: Option<String>,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let chapters = match book.extension().and_then(|e| e.to_str()) {
+        Some("epub") => read_epub_chapters(&book)?,
+        _ => read_text_chapters(&book)?,
+    };
+    if chapters.is_empty() {
+        return Err(format!("No chapters with text found in {}", book.display()).into());
+    }
+
+    let config = load_config(None).unwrap_or_default();
+    let voice = config.resolve_voice(voice.as_deref().unwrap_or(&config.default_voice));
+    let book_title = title.unwrap_or_else(|| {
+        book.file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Audiobook".to_string())
+    });
+
+    if !json {
+        println!(
+            "📖 Narrating '{}': {} chapter(s) with {}",
+            book_title,
+            chapters.len(),
+            voice
+        );
+    }
+    std::fs::create_dir_all(&out)?;
+
+    let client = TTSClient::new(Some(config.clone()));
+    let reporter = BarReporter::new("Chapters");
+    let mut synthesized = Vec::new();
+    let mut outputs: Vec<PathBuf> = Vec::new();
+    let mut total_bytes = 0u64;
+    for (i, (chapter_title, text)) in chapters.iter().enumerate() {
+        let audio_data = client.synthesize_long_text(text, &voice).await?;
+        total_bytes += audio_data.len() as u64;
+
+        if m4b {
+            synthesized.push(hello_edge_tts::audio_processing::Chapter::new(
+                chapter_title.clone(),
+                audio_data,
+            ));
+        } else {
+            let path = out.join(format!("{:02}_{}.mp3", i + 1, slugify(chapter_title)));
+            client
+                .save_audio_with_tags(
+                    &audio_data,
+                    path.to_str().unwrap(),
+                    &hello_edge_tts::AudioTags {
+                        title: Some(chapter_title.clone()),
+                        artist: author.clone(),
+                        album: Some(book_title.clone()),
+                        track: Some(i as u32 + 1),
+                        language: None,
+                        lyrics: None,
+                    },
+                )
+                .await?;
+            record_history(&voice, text, &audio_data, &path.display().to_string());
+            outputs.push(path);
+        }
+        reporter.on_progress(i + 1, chapters.len(), total_bytes);
+    }
+    reporter.finish();
+
+    if m4b {
+        let output = out.join(format!("{}.m4b", slugify(&book_title)));
+        hello_edge_tts::audio_processing::export_audiobook(
+            &synthesized,
+            &hello_edge_tts::AudioTags {
+                title: Some(book_title.clone()),
+                artist: author,
+                album: None,
+                track: None,
+                language: None,
+                lyrics: None,
+            },
+            &output,
+        )
+        .await?;
+        outputs.push(output);
+    }
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "title": book_title,
+                "voice": voice,
+                "chapters": chapters.len(),
+                "bytes": total_bytes,
+                "outputs": outputs,
+            })
+        );
+    } else {
+        println!("🎉 Audiobook complete:");
+        for path in &outputs {
+            println!("   {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Cache manifest written into the output directory so unchanged source
+/// files are skipped across runs
+const WATCH_CACHE_FILE: &str = ".hello-edge-tts-cache.json";
+
+/// Hex SHA-256 over the given parts, for change-detection caches and
+/// checkpoints
+fn content_digest(parts: &[&str]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn is_watched_text_file(path: &std::path::Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| matches!(e, "txt" | "md"))
+}
+
+fn collect_text_files(dir: &std::path::Path, found: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_text_files(&path, found)?;
+        } else if is_watched_text_file(&path) {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Synthesize one watched file if its content (or the voice/format it
+/// would be rendered with) changed since the cached run. Returns whether
+/// audio was actually produced.
+async fn synthesize_watched_file(
+    client: &TTSClient,
+    config: &TTSConfig,
+    voice: &str,
+    dir: &std::path::Path,
+    out: &std::path::Path,
+    path: &std::path::Path,
+    cache: &mut std::collections::HashMap<String, String>,
+) -> Result<bool, Box<dyn std::error::Error>> {
+    let text = std::fs::read_to_string(path)?;
+    if text.trim().is_empty() {
+        return Ok(false);
+    }
+
+    let relative = path.strip_prefix(dir).unwrap_or(path);
+    let key = relative.to_string_lossy().to_string();
+
+    let digest = content_digest(&[&text, voice, &config.output_format]);
+    if cache.get(&key) == Some(&digest) {
+        return Ok(false);
+    }
+
+    let output = out
+        .join(relative)
+        .with_extension(&config.output_format);
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let audio_data = client.synthesize_long_text(&text, voice).await?;
+    client
+        .save_audio(&audio_data, &output.to_string_lossy())
+        .await?;
+    record_history(voice, &text, &audio_data, &output.display().to_string());
+    cache.insert(key, digest);
+    println!("🔊 {} -> {}", relative.display(), output.display());
+    Ok(true)
+}
+
+async fn handle_watch(
+    dir: PathBuf,
+    out: PathBuf,
+    voice: Option<String>,
+    format: Option<OutputFormat>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+    let dir = dir
+        .canonicalize()
+        .map_err(|e| format!("Cannot watch {}: {}", dir.display(), e))?;
+
+    let mut config = load_config(None).unwrap_or_default();
+    if let Some(format) = format {
+        config.output_format = format.as_str().to_string();
+    }
+    let client = TTSClient::new(Some(config.clone()));
+    let voice = config.resolve_voice(voice.as_deref().unwrap_or(&config.default_voice));
+
+    std::fs::create_dir_all(&out)?;
+    let cache_path = out.join(WATCH_CACHE_FILE);
+    let mut cache: std::collections::HashMap<String, String> = std::fs::read_to_string(&cache_path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default();
+
+    // Catch up on whatever already exists before waiting for changes
+    let mut existing = Vec::new();
+    collect_text_files(&dir, &mut existing)?;
+    existing.sort();
+    for path in &existing {
+        if let Err(e) =
+            synthesize_watched_file(&client, &config, &voice, &dir, &out, path, &mut cache).await
+        {
+            eprintln!("❌ {}: {}", path.display(), e);
+        }
+    }
+    std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)?;
+
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event {
+            if matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+                for path in event.paths {
+                    let _ = tx.send(path);
+                }
+            }
+        }
+    })?;
+    watcher.watch(&dir, RecursiveMode::Recursive)?;
+
+    println!(
+        "👀 Watching {} -> {} (voice: {}, format: {}), Ctrl+C to stop",
+        dir.display(),
+        out.display(),
+        voice,
+        config.output_format
+    );
+
+    while let Some(path) = rx.recv().await {
+        if !is_watched_text_file(&path) || !path.exists() {
+            continue;
+        }
+        // The content digest makes repeated events for one write harmless
+        match synthesize_watched_file(&client, &config, &voice, &dir, &out, &path, &mut cache).await
+        {
+            Ok(true) => {
+                std::fs::write(&cache_path, serde_json::to_string_pretty(&cache)?)?;
+            }
+            Ok(false) => {}
+            Err(e) => eprintln!("❌ {}: {}", path.display(), e),
+        }
+    }
+
+    Ok(())
+}
+
+/// One line of the synthesis history log
+#[derive(serde::Serialize, serde::Deserialize)]
+struct HistoryEntry {
+    timestamp: chrono::DateTime<chrono::Utc>,
+    voice: String,
+    characters: usize,
+    bytes: usize,
+    /// Seconds of audio produced; 0.0 when the output could not be probed
+    #[serde(default)]
+    duration_secs: f64,
+    output: String,
+}
+
+/// Where past syntheses are logged, one JSON object per line so the file
+/// appends cheaply and greps well
+fn history_path() -> PathBuf {
+    match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home).join(".tts").join("history.jsonl"),
+        None => PathBuf::from("./tts_history.jsonl"),
+    }
+}
+
+/// Append one synthesis to the history log, best effort: bookkeeping must
+/// never fail the synthesis it describes
+fn record_history(voice: &str, text: &str, audio_data: &[u8], output: &str) {
+    let entry = HistoryEntry {
+        timestamp: chrono::Utc::now(),
+        voice: voice.to_string(),
+        characters: text.chars().count(),
+        bytes: audio_data.len(),
+        duration_secs: hello_edge_tts::audio_processing::probe(audio_data)
+            .map(|info| info.duration.as_secs_f64())
+            .unwrap_or_default(),
+        output: output.to_string(),
+    };
+    let Ok(line) = serde_json::to_string(&entry) else {
+        return;
+    };
+    let path = history_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    use std::io::Write;
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(&path) {
+        let _ = writeln!(file, "{}", line);
+    }
+}
+
+/// Load the whole history, skipping lines that do not parse so an old or
+/// hand-edited log never blocks the review commands
+fn load_history() -> Vec<HistoryEntry> {
+    std::fs::read_to_string(history_path())
+        .map(|content| {
+            content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn handle_history(
+    limit: usize,
+    voice: Option<String>,
+    clear: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if clear {
+        let path = history_path();
+        if path.exists() {
+            std::fs::remove_file(&path)?;
+        }
+        if json {
+            println!("{}", serde_json::json!({ "status": "ok", "cleared": true }));
+        } else {
+            println!("✅ History cleared");
+        }
+        return Ok(());
+    }
+
+    let mut entries = load_history();
+    if let Some(voice) = &voice {
+        entries.retain(|entry| entry.voice.contains(voice.as_str()));
+    }
+    let start = entries.len().saturating_sub(limit);
+    let recent = &entries[start..];
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "total": entries.len(),
+                "entries": recent.iter().map(|entry| serde_json::json!({
+                    "timestamp": entry.timestamp,
+                    "voice": entry.voice,
+                    "characters": entry.characters,
+                    "bytes": entry.bytes,
+                    "duration_secs": entry.duration_secs,
+                    "output": entry.output,
+                })).collect::<Vec<_>>(),
+            })
+        );
+        return Ok(());
+    }
+
+    if recent.is_empty() {
+        println!("📜 No history yet — synthesize something first");
+        return Ok(());
+    }
+    println!(
+        "📜 Last {} of {} synthesis job(s)",
+        recent.len(),
+        entries.len()
+    );
+    for entry in recent.iter().rev() {
+        println!(
+            "{}  {:<32} {:>6} chars {:>6.1}s  {}",
+            entry.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            entry.voice,
+            entry.characters,
+            entry.duration_secs,
+            entry.output
+        );
+    }
+    Ok(())
+}
+
+async fn handle_stats(json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let entries = load_history();
+    if entries.is_empty() {
+        if json {
+            println!("{}", serde_json::json!({ "status": "ok", "jobs": 0 }));
+        } else {
+            println!("📊 No history yet — synthesize something first");
+        }
+        return Ok(());
+    }
+
+    let characters: usize = entries.iter().map(|entry| entry.characters).sum();
+    let bytes: usize = entries.iter().map(|entry| entry.bytes).sum();
+    let audio_secs: f64 = entries.iter().map(|entry| entry.duration_secs).sum();
+    let mut by_voice: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for entry in &entries {
+        *by_voice.entry(entry.voice.as_str()).or_default() += 1;
+    }
+    let mut by_voice: Vec<(&str, usize)> = by_voice.into_iter().collect();
+    by_voice.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    let first = entries.iter().map(|entry| entry.timestamp).min().unwrap();
+    let last = entries.iter().map(|entry| entry.timestamp).max().unwrap();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "jobs": entries.len(),
+                "characters": characters,
+                "bytes": bytes,
+                "audio_secs": audio_secs,
+                "first": first,
+                "last": last,
+                "by_voice": by_voice.iter().map(|(voice, count)| serde_json::json!({
+                    "voice": voice,
+                    "jobs": count,
+                })).collect::<Vec<_>>(),
+            })
+        );
+        return Ok(());
+    }
+
+    println!("📊 Synthesis usage");
+    println!("Jobs:       {}", entries.len());
+    println!("Characters: {}", characters);
+    println!("Audio:      {:.1} min", audio_secs / 60.0);
+    println!("Bytes:      {:.1} MB", bytes as f64 / (1024.0 * 1024.0));
+    println!(
+        "Span:       {} — {}",
+        first.format("%Y-%m-%d"),
+        last.format("%Y-%m-%d")
+    );
+    println!("Top voices:");
+    for (voice, count) in by_voice.iter().take(10) {
+        println!("  {:<40} {}", voice, count);
+    }
+    Ok(())
+}
+
+/// Paths checked for user-registered templates: a JSON object mapping
+/// template names to text with `{placeholder}`s, `{text}` included
+const USER_TEMPLATE_PATHS: &[&str] = &["./tts_templates.json", "~/.tts/templates.json"];
+
+fn load_user_templates() -> std::collections::HashMap<String, String> {
+    USER_TEMPLATE_PATHS
+        .iter()
+        .map(|p| match p.strip_prefix("~/") {
+            Some(rest) => match std::env::var_os("HOME") {
+                Some(home) => PathBuf::from(home).join(rest),
+                None => PathBuf::from(p),
+            },
+            None => PathBuf::from(p),
+        })
+        .find_map(|path| {
+            let content = std::fs::read_to_string(path).ok()?;
+            serde_json::from_str(&content).ok()
+        })
+        .unwrap_or_default()
+}
+
+/// Render a built-in or user-registered template into SSML
+fn render_template(
+    name: &str,
+    text: &str,
+    voice: &str,
+    values: &[String],
+) -> Result<String, Box<dyn std::error::Error>> {
+    if SSMLTemplates::get_available_templates().contains(&name) {
+        return Ok(SSMLTemplates::create_from_template(name, text, voice)?);
+    }
+
+    let user_templates = load_user_templates();
+    let Some(template) = user_templates.get(name) else {
+        let mut available: Vec<String> = SSMLTemplates::get_available_templates()
+            .into_iter()
+            .map(String::from)
+            .chain(user_templates.keys().cloned())
+            .collect();
+        available.sort();
+        return Err(format!(
+            "Unknown template '{}'. Available: {}",
+            name,
+            available.join(", ")
+        )
+        .into());
+    };
+
+    let mut map = std::collections::HashMap::new();
+    for pair in values {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid --set '{}'; expected KEY=VALUE", pair))?;
+        map.insert(key.trim().to_string(), value.to_string());
+    }
+    map.entry("text".to_string())
+        .or_insert_with(|| text.to_string());
+    Ok(SSMLTemplates::create_from_custom_template(
+        template, &map, voice,
+    )?)
+}
+
+async fn handle_template(
+    action: TemplateAction,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        TemplateAction::List => {
+            let user_templates = load_user_templates();
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "builtin": SSMLTemplates::get_available_templates(),
+                        "user": user_templates.keys().collect::<Vec<_>>(),
+                    })
+                );
+            } else {
+                println!("Built-in templates:");
+                for name in SSMLTemplates::get_available_templates() {
+                    println!("   {}", name);
+                }
+                if user_templates.is_empty() {
+                    println!(
+                        "No user templates; register some in {}",
+                        USER_TEMPLATE_PATHS.join(" or ")
+                    );
+                } else {
+                    println!("User templates:");
+                    let mut names: Vec<_> = user_templates.keys().collect();
+                    names.sort();
+                    for name in names {
+                        println!("   {}", name);
+                    }
+                }
+            }
+        }
+        TemplateAction::Show { name, text } => {
+            let ssml = render_template(&name, &text, "en-US-AriaNeural", &[])?;
+            if json {
+                println!("{}", serde_json::json!({ "name": name, "ssml": ssml }));
+            } else {
+                println!("{}", pretty_print_ssml(&ssml).trim_end());
+            }
+        }
+        TemplateAction::Apply {
+            name,
+            text,
+            voice,
+            values,
+            output,
+            play,
+        } => {
+            let config = load_config(None).unwrap_or_default();
+            let voice = config.resolve_voice(&voice);
+            let ssml = render_template(&name, &text, &voice, &values)?;
+
+            let client = TTSClient::new(Some(config.clone()));
+            let audio_data = client.synthesize_ssml(&ssml, &voice).await?;
+            let output_path = output.unwrap_or_else(|| {
+                config.resolve_output_path(&format!("template_{}", name))
+            });
+            client
+                .save_audio(&audio_data, output_path.to_str().unwrap())
+                .await?;
+            record_history(&voice, &ssml, &audio_data, &output_path.display().to_string());
+
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({
+                        "status": "ok",
+                        "template": name,
+                        "voice": voice,
+                        "bytes": audio_data.len(),
+                        "output": output_path,
+                    })
+                );
+            } else {
+                println!("✅ Audio saved to: {}", output_path.display());
+            }
+
+            if play {
+                match AudioPlayer::new() {
+                    Ok(player) => {
+                        if let Err(e) = player.play_file(output_path.to_str().unwrap()) {
+                            eprintln!("❌ Failed to play audio: {}", e);
+                        }
+                    }
+                    Err(e) => eprintln!("❌ Failed to create audio player: {}", e),
+                }
+            }
+        }
+    }
+    Ok(())
+}
 
-    match cli.command {
-        Commands::Speak {
-            text,
-            voice,
-            output,
-            play,
-        } => {
-            handle_speak(text, voice, output, play).await?;
+fn handle_config(action: ConfigAction, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    match action {
+        ConfigAction::Init { preset, force } => {
+            let path = ConfigManager::preferred_config_path();
+            if std::path::Path::new(&path).exists() && !force {
+                return Err(format!(
+                    "Config file {} already exists; pass --force to overwrite",
+                    path
+                )
+                .into());
+            }
+            create_default_config(&path, &preset)?;
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "status": "ok", "path": path, "preset": preset })
+                );
+            } else {
+                println!("✅ Created {} from preset '{}'", path, preset);
+            }
+        }
+        ConfigAction::Show => {
+            let config = load_config(None)?;
+            println!("{}", serde_json::to_string_pretty(&config)?);
         }
-        Commands::Voices { language, detailed } => {
-            handle_voices(language, detailed).await?;
+        ConfigAction::Get { key } => {
+            let config = load_config(None)?;
+            let value = serde_json::to_value(&config)?;
+            match value.get(&key) {
+                // Bare strings print unquoted so values compose in scripts
+                Some(serde_json::Value::String(s)) if !json => println!("{}", s),
+                Some(value) => println!("{}", value),
+                None => return Err(format!("Unknown config key '{}'", key).into()),
+            }
         }
-        Commands::Demo { language } => {
-            handle_demo(language).await?;
+        ConfigAction::Set { key, value } => {
+            let path = ConfigManager::preferred_config_path();
+            let config = ConfigManager::set_config_value(&path, &key, &value)?;
+            let stored = serde_json::to_value(&config)?[&key].clone();
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "status": "ok", "path": path, "key": key, "value": stored })
+                );
+            } else {
+                println!("✅ {} = {} (in {})", key, stored, path);
+            }
         }
+        ConfigAction::Path => match ConfigManager::find_config_file() {
+            Some(path) => println!("{}", path),
+            None => println!(
+                "{} (not created yet)",
+                ConfigManager::preferred_config_path()
+            ),
+        },
     }
-
     Ok(())
 }
 
-async fn handle_speak(
+/// Body of a POST /synthesize request
+#[derive(serde::Deserialize)]
+struct SynthesizeRequest {
     text: String,
-    voice: String,
-    output: Option<PathBuf>,
-    play: bool,
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🎤 Converting text to speech...");
-    println!("Text: {}", text);
-    println!("Voice: {}", voice);
+    #[serde(default)]
+    voice: Option<String>,
+    #[serde(default)]
+    ssml: bool,
+}
 
-    let mut client = TTSClient::new(None);
+async fn handle_serve(port: u16) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    println!("🌐 Serving on http://127.0.0.1:{}", port);
+    println!("   GET  /voices            voice catalog as JSON");
+    println!("   POST /synthesize        {{\"text\", \"voice\", \"ssml\"}} in, audio out");
 
-    // Verify the voice exists
-    match client.list_voices().await {
-        Ok(voices) => {
-            if !voices.iter().any(|v| v.name == voice) {
-                eprintln!("❌ Voice '{}' not found!", voice);
-                eprintln!("💡 Use 'hello-edge-tts voices' to see available voices");
-                return Ok(());
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(async move {
+            if let Err(e) = handle_http_connection(stream).await {
+                eprintln!("❌ Request failed: {}", e);
             }
-        }
-        Err(e) => {
-            eprintln!("❌ Failed to list voices: {}", e);
+        });
+    }
+}
+
+async fn handle_http_connection(
+    mut stream: tokio::net::TcpStream,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    // Read the head, then exactly Content-Length bytes of body
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
             return Ok(());
         }
+        buffer.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+        if buffer.len() > 64 * 1024 {
+            return Err("Request head too large".into());
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..head_end]).to_string();
+    let mut lines = head.lines();
+    let request_line = lines.next().unwrap_or_default().to_string();
+    let content_length = lines
+        .filter_map(|l| l.split_once(':'))
+        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.trim().parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < head_end + content_length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
     }
+    let body = &buffer[head_end..(head_end + content_length).min(buffer.len())];
 
-    // Attempt synthesis (will show demo message since WebSocket implementation is complex)
-    match client.synthesize_text(&text, &voice, None).await {
-        Ok(audio_data) => {
-            let output_path = output.unwrap_or_else(|| {
-                // Extract language from voice (e.g., 'en' from 'en-US-AriaNeural')
-                let lang = voice.split('-').next().unwrap_or("unknown");
-                let timestamp = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-                PathBuf::from(format!("edgetts_{}_rust_{}.mp3", lang, timestamp))
-            });
+    let (status, content_type, payload) = route_request(&request_line, body).await;
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        content_type,
+        payload.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.write_all(&payload).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+async fn route_request(request_line: &str, body: &[u8]) -> (&'static str, &'static str, Vec<u8>) {
+    let json_error = |status: &'static str, message: String| {
+        (
+            status,
+            "application/json",
+            serde_json::json!({ "error": message }).to_string().into_bytes(),
+        )
+    };
 
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = (parts.next().unwrap_or(""), parts.next().unwrap_or(""));
+    match (method, path) {
+        ("GET", "/voices") => {
+            let mut client = TTSClient::new(None);
+            match client.list_voices().await {
+                Ok(voices) => match serde_json::to_vec(&voices) {
+                    Ok(payload) => ("200 OK", "application/json", payload),
+                    Err(e) => json_error("500 Internal Server Error", e.to_string()),
+                },
+                Err(e) => json_error("502 Bad Gateway", e.to_string()),
+            }
+        }
+        ("POST", "/synthesize") => {
+            let request: SynthesizeRequest = match serde_json::from_slice(body) {
+                Ok(request) => request,
+                Err(e) => return json_error("400 Bad Request", format!("Invalid JSON: {}", e)),
+            };
+            let config = load_config(None).unwrap_or_default();
+            let voice = config.resolve_voice(
+                request.voice.as_deref().unwrap_or(&config.default_voice),
+            );
+            let client = TTSClient::new(Some(config));
             match client
-                .save_audio(&audio_data, output_path.to_str().unwrap())
+                .synthesize_text(&request.text, &voice, Some(request.ssml))
                 .await
             {
-                Ok(()) => {
-                    println!("✅ Audio saved to: {}", output_path.display());
-
-                    if play {
-                        println!("🔊 Playing audio...");
-                        match AudioPlayer::new() {
-                            Ok(player) => {
-                                if let Err(e) = player.play_file(output_path.to_str().unwrap()) {
-                                    eprintln!("❌ Failed to play audio: {}", e);
-                                }
-                            }
-                            Err(e) => {
-                                eprintln!("❌ Failed to create audio player: {}", e);
-                            }
+                Ok(audio_data) => ("200 OK", "audio/mpeg", audio_data),
+                Err(e) => json_error("500 Internal Server Error", e.to_string()),
+            }
+        }
+        _ => json_error("404 Not Found", format!("No route for {} {}", method, path)),
+    }
+}
+
+async fn handle_interactive(voice: Option<String>) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{BufRead, Write};
+
+    let config = load_config(None).unwrap_or_default();
+    let mut voice = config.resolve_voice(&voice.unwrap_or_else(|| config.default_voice.clone()));
+    let mut config = config;
+    // One client for the whole session, rebuilt only when settings change
+    let mut client = TTSClient::new(Some(config.clone()));
+    let player = AudioPlayer::new()?;
+    let mut last_audio: Option<Vec<u8>> = None;
+
+    println!("🎙️  Interactive mode — type text to speak it, /help for commands, /quit to exit");
+    println!("Voice: {}", voice);
+
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            break;
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(command) = line.strip_prefix('/') {
+            let (command, argument) = command.split_once(' ').unwrap_or((command, ""));
+            let argument = argument.trim();
+            match command {
+                "quit" | "exit" => break,
+                "help" => {
+                    println!("/voice <name>  switch voice");
+                    println!("/rate <value>  set speaking rate, e.g. +20%");
+                    println!("/save <path>   save the last spoken audio");
+                    println!("/quit          leave interactive mode");
+                }
+                "voice" if !argument.is_empty() => {
+                    voice = config.resolve_voice(argument);
+                    println!("Voice: {}", voice);
+                }
+                "rate" if !argument.is_empty() => {
+                    let previous = std::mem::replace(&mut config.rate, argument.to_string());
+                    if let Err(e) = config.validate() {
+                        eprintln!("❌ {}", e);
+                        config.rate = previous;
+                    } else {
+                        client = TTSClient::new(Some(config.clone()));
+                        println!("Rate: {}", config.rate);
+                    }
+                }
+                "save" if !argument.is_empty() => match &last_audio {
+                    Some(audio_data) => {
+                        match client.save_audio(audio_data, argument).await {
+                            Ok(()) => println!(
+                                "✅ Saved to {}",
+                                config.resolve_output_path(argument).display()
+                            ),
+                            Err(e) => eprintln!("❌ {}", e),
                         }
                     }
+                    None => eprintln!("❌ Nothing spoken yet"),
+                },
+                _ => eprintln!("❌ Unknown command; try /help"),
+            }
+            continue;
+        }
+
+        match client.synthesize_text(line, &voice, None).await {
+            Ok(audio_data) => {
+                if let Err(e) = player.play_audio_data(audio_data.clone(), None) {
+                    eprintln!("❌ Playback failed: {}", e);
                 }
-                Err(e) => {
-                    eprintln!("❌ Failed to save audio: {}", e);
+                last_audio = Some(audio_data);
+            }
+            Err(e) => eprintln!("❌ Synthesis failed: {}", e),
+        }
+    }
+
+    println!("👋 Leaving interactive mode");
+    Ok(())
+}
+
+async fn handle_preview(
+    language: String,
+    text: Option<String>,
+    limit: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut client = TTSClient::new(None);
+
+    println!("🎧 Fetching voices for language '{}'...", language);
+    let voices = client.get_voices_by_language(&language).await?;
+    if voices.is_empty() {
+        eprintln!("❌ No voices found for language '{}'", language);
+        return Ok(());
+    }
+
+    let sample = text.unwrap_or_else(|| "Hello! This is a short voice preview.".to_string());
+    let player = AudioPlayer::new()?;
+    let count = voices.len().min(limit);
+    println!("Auditioning {} of {} voice(s)...", count, voices.len());
+
+    for (i, voice) in voices.iter().take(limit).enumerate() {
+        println!(
+            "🎤 [{}/{}] {} ({}, {})",
+            i + 1,
+            count,
+            voice.display_name,
+            voice.locale,
+            voice.gender
+        );
+        match client.synthesize_text(&sample, &voice.name, None).await {
+            Ok(audio_data) => {
+                if let Err(e) = player.play_audio_data(audio_data, None) {
+                    eprintln!("   ❌ Playback failed: {}", e);
                 }
             }
+            Err(e) => eprintln!("   ❌ Synthesis failed: {}", e),
+        }
+        // A beat of silence so consecutive voices are easy to tell apart
+        if i + 1 < count {
+            tokio::time::sleep(std::time::Duration::from_millis(700)).await;
+        }
+    }
+
+    println!("🎉 Preview finished. Use --voice with 'speak' to pick one.");
+    Ok(())
+}
+
+fn handle_play(
+    files: Vec<PathBuf>,
+    volume: Option<f32>,
+    speed: Option<f32>,
+    backend: Option<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let player = match backend {
+        Some(name) => AudioPlayer::new_with_backend(&name)?,
+        None => AudioPlayer::new()?,
+    };
+    if player.is_null() {
+        eprintln!("⚠️  No audio device available; decoding without playback");
+    }
+    if let Some(v) = volume {
+        player.set_volume(v);
+    }
+    if let Some(s) = speed {
+        player.set_speed(s);
+    }
+
+    // Queue everything up front so files play back to back without gaps
+    for file in &files {
+        let data = std::fs::read(file)?;
+        let hint = file.extension().and_then(|e| e.to_str());
+        player.queue_audio_data(data, hint)?;
+        match AudioPlayer::duration_of_file(&file.display().to_string()) {
+            Ok(duration) => println!("▶️  {} ({:.1}s)", file.display(), duration.as_secs_f64()),
+            Err(_) => println!("▶️  {}", file.display()),
+        }
+    }
+
+    player.wait_until_end();
+    Ok(())
+}
+
+async fn handle_compare(
+    text: String,
+    voices: Vec<String>,
+    out: PathBuf,
+    play: bool,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let config = load_config(None).unwrap_or_default();
+    let mut client = TTSClient::new(Some(config.clone()));
+
+    let catalog = client.list_voices().await?;
+    let voices: Vec<String> = voices
+        .iter()
+        .map(|voice| config.resolve_voice(voice.trim()))
+        .collect();
+    for voice in &voices {
+        if !catalog.iter().any(|v| &v.name == voice) {
+            return Err(CliError::VoiceNotFound(voice.clone()).into());
+        }
+    }
+
+    std::fs::create_dir_all(&out)?;
+    if !json {
+        println!("🎭 Comparing {} voice(s)", voices.len());
+        println!("Text: {}", text);
+    }
+
+    let mut outputs = Vec::new();
+    for voice in &voices {
+        let audio_data = client.synthesize_text(&text, voice, None).await?;
+        let path = out.join(format!("{}.{}", voice, config.output_format));
+        client.save_audio(&audio_data, path.to_str().unwrap()).await?;
+        record_history(voice, &text, &audio_data, &path.display().to_string());
+        if !json {
+            println!("✅ {} -> {}", voice, path.display());
         }
-        Err(e) => {
-            eprintln!("❌ TTS synthesis failed: {}", e);
-            eprintln!("💡 This is a demo implementation. Full WebSocket support needed for actual synthesis.");
+        outputs.push((voice.clone(), audio_data, path));
+    }
+
+    if play {
+        let player = AudioPlayer::new()?;
+        if player.is_null() {
+            eprintln!("⚠️  No audio device available; skipping playback");
+        } else {
+            for (voice, audio_data, _) in &outputs {
+                // Announce with the voice itself, using the friendly name
+                // so the announcement is speakable
+                let spoken = catalog
+                    .iter()
+                    .find(|v| &v.name == voice)
+                    .map(|v| v.display_name.as_str())
+                    .unwrap_or(voice);
+                if !json {
+                    println!("🔊 {}", voice);
+                }
+                let announcement = client
+                    .synthesize_text(&format!("This is {}.", spoken), voice, None)
+                    .await?;
+                player.queue_audio_data(announcement, Some(&config.output_format))?;
+                player.queue_audio_data(audio_data.clone(), Some(&config.output_format))?;
+                player.wait_until_end();
+            }
         }
     }
 
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "text": text,
+                "outputs": outputs.iter().map(|(voice, audio_data, path)| serde_json::json!({
+                    "voice": voice,
+                    "bytes": audio_data.len(),
+                    "output": path,
+                })).collect::<Vec<_>>(),
+            })
+        );
+    } else {
+        println!("🎉 Wrote {} file(s) to {}", outputs.len(), out.display());
+    }
     Ok(())
 }
 
 async fn handle_voices(
     language: Option<String>,
     detailed: bool,
+    export: Option<ExportFormat>,
+    output: Option<PathBuf>,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🎵 Fetching available voices...");
+    // Keep stdout clean for exports so they can be piped
+    let exporting = export.is_some();
+    if !exporting {
+        println!("🎵 Fetching available voices...");
+    }
 
     let mut client = TTSClient::new(None);
 
     let voices = match language {
         Some(lang) => {
-            println!("Filtering by language: {}", lang);
+            if !exporting {
+                println!("Filtering by language: {}", lang);
+            }
             client.get_voices_by_language(&lang).await?
         }
         None => client.list_voices().await?,
     };
 
     if voices.is_empty() {
-        println!("No voices found for the specified criteria.");
+        eprintln!("No voices found for the specified criteria.");
+        return Ok(());
+    }
+
+    if let Some(format) = export {
+        let rendered = format.render(&voices)?;
+        match output {
+            Some(path) => {
+                std::fs::write(&path, rendered)?;
+                eprintln!("✅ Exported {} voice(s) to {}", voices.len(), path.display());
+            }
+            None => print!("{}", rendered),
+        }
         return Ok(());
     }
 
+    // Configured aliases display next to the voices they point at, so
+    // scripts can use the short names they see here
+    let config = load_config(None).unwrap_or_default();
+    let mut aliases_by_voice: std::collections::HashMap<&str, Vec<&str>> =
+        std::collections::HashMap::new();
+    for (alias, target) in &config.voice_aliases {
+        aliases_by_voice
+            .entry(target.as_str())
+            .or_default()
+            .push(alias.as_str());
+    }
+    for aliases in aliases_by_voice.values_mut() {
+        aliases.sort_unstable();
+    }
+
     println!("\n📋 Available voices ({} total):", voices.len());
     println!("{}", "=".repeat(60));
 
@@ -179,6 +3042,9 @@ async fn handle_voices(
             println!("   Locale: {}", voice.locale);
             println!("   Gender: {}", voice.gender);
             println!("   Language: {}", voice.language_code());
+            if let Some(aliases) = aliases_by_voice.get(voice.name.as_str()) {
+                println!("   Aliases: {}", aliases.join(", "));
+            }
             println!();
         }
     } else {
@@ -189,7 +3055,7 @@ async fn handle_voices(
         for voice in voices {
             by_language
                 .entry(voice.language_code().to_string())
-                .or_insert_with(Vec::new)
+                .or_default()
                 .push(voice);
         }
 
@@ -197,9 +3063,13 @@ async fn handle_voices(
             voices.sort_by(|a, b| a.display_name.cmp(&b.display_name));
             println!("\n🌍 {} ({} voices):", lang.to_uppercase(), voices.len());
             for voice in voices {
+                let alias_note = aliases_by_voice
+                    .get(voice.name.as_str())
+                    .map(|aliases| format!(" [alias: {}]", aliases.join(", ")))
+                    .unwrap_or_default();
                 println!(
-                    "  • {} ({}) - {}",
-                    voice.display_name, voice.locale, voice.gender
+                    "  • {} ({}) - {}{}",
+                    voice.display_name, voice.locale, voice.gender, alias_note
                 );
             }
         }
@@ -208,7 +3078,434 @@ async fn handle_voices(
     Ok(())
 }
 
-async fn handle_demo(language: String) -> Result<(), Box<dyn std::error::Error>> {
+/// One timed synthesis run: time to the first audio chunk, total wall
+/// time, and how much audio came back
+struct BenchRun {
+    ttfb: std::time::Duration,
+    total: std::time::Duration,
+    bytes: usize,
+    audio: std::time::Duration,
+}
+
+async fn handle_bench(
+    voice: String,
+    text: String,
+    iterations: usize,
+    json: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use futures_util::StreamExt;
+    use std::time::Instant;
+
+    let config = load_config(None).unwrap_or_default();
+    let voice = config.resolve_voice(&voice);
+    let mut client = TTSClient::new(Some(config));
+
+    // Time the voice catalog both cold and from the cache, since listing
+    // is on the hot path of every subcommand that validates a voice
+    let started = Instant::now();
+    let voices = client.list_voices().await?;
+    let voices_cold = started.elapsed();
+    let started = Instant::now();
+    client.list_voices().await?;
+    let voices_cached = started.elapsed();
+
+    if !voices.iter().any(|v| v.name == voice) {
+        return Err(CliError::VoiceNotFound(voice).into());
+    }
+
+    if !json {
+        println!("📊 Benchmarking '{}', {} iteration(s)", voice, iterations);
+        println!("Text: {} chars", text.chars().count());
+        println!();
+    }
+
+    let mut runs: Vec<BenchRun> = Vec::with_capacity(iterations);
+    for i in 0..iterations.max(1) {
+        let started = Instant::now();
+        let mut ttfb = None;
+        let mut audio_data = Vec::new();
+        let mut stream = Box::pin(client.synthesize_stream(&text, &voice));
+        while let Some(item) = stream.next().await {
+            let chunk = item?;
+            ttfb.get_or_insert_with(|| started.elapsed());
+            audio_data.extend_from_slice(&chunk);
+        }
+        let total = started.elapsed();
+        let audio = hello_edge_tts::audio_processing::probe(&audio_data)
+            .map(|info| info.duration)
+            .unwrap_or_default();
+        if !json {
+            println!(
+                "  run {}/{}: first byte {:.0?}, total {:.0?}",
+                i + 1,
+                iterations.max(1),
+                ttfb.unwrap_or(total),
+                total
+            );
+        }
+        runs.push(BenchRun {
+            ttfb: ttfb.unwrap_or(total),
+            total,
+            bytes: audio_data.len(),
+            audio,
+        });
+    }
+
+    let ms = |d: std::time::Duration| d.as_secs_f64() * 1000.0;
+    let totals: Vec<f64> = runs.iter().map(|r| ms(r.total)).collect();
+    let min = totals.iter().cloned().fold(f64::INFINITY, f64::min);
+    let max = totals.iter().cloned().fold(0.0, f64::max);
+    let mean = totals.iter().sum::<f64>() / totals.len() as f64;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": "ok",
+                "voice": voice,
+                "text_chars": text.chars().count(),
+                "voices_cold_ms": ms(voices_cold),
+                "voices_cached_ms": ms(voices_cached),
+                "runs": runs.iter().map(|r| serde_json::json!({
+                    "ttfb_ms": ms(r.ttfb),
+                    "total_ms": ms(r.total),
+                    "bytes": r.bytes,
+                    "audio_ms": ms(r.audio),
+                })).collect::<Vec<_>>(),
+                "total_ms": { "min": min, "mean": mean, "max": max },
+            })
+        );
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "{:<6} {:>10} {:>10} {:>10} {:>12} {:>8}",
+        "Run", "TTFB", "Total", "Bytes", "Throughput", "xRT"
+    );
+    for (i, run) in runs.iter().enumerate() {
+        let secs = run.total.as_secs_f64();
+        let throughput = if secs > 0.0 {
+            run.bytes as f64 / 1024.0 / secs
+        } else {
+            0.0
+        };
+        // Real-time factor: seconds of audio produced per second of wall
+        // time; above 1.0 means faster than playback
+        let xrt = if secs > 0.0 {
+            run.audio.as_secs_f64() / secs
+        } else {
+            0.0
+        };
+        println!(
+            "{:<6} {:>8.0}ms {:>8.0}ms {:>10} {:>7.1} KB/s {:>7.2}x",
+            i + 1,
+            ms(run.ttfb),
+            ms(run.total),
+            run.bytes,
+            throughput,
+            xrt
+        );
+    }
+    println!();
+    println!(
+        "Total latency: min {:.0}ms / mean {:.0}ms / max {:.0}ms",
+        min, mean, max
+    );
+    println!(
+        "Voice catalog: cold {:.0}ms, cached {:.2}ms",
+        ms(voices_cold),
+        ms(voices_cached)
+    );
+    Ok(())
+}
+
+/// Outcome of one environment check; `hint` tells the user how to fix a
+/// failure rather than just naming it
+struct DoctorCheck {
+    name: &'static str,
+    ok: bool,
+    detail: String,
+    hint: Option<&'static str>,
+}
+
+/// Whether `command args...` runs and exits successfully, for probing the
+/// synthesis backends on the PATH
+async fn command_succeeds(command: &str, args: &[&str]) -> bool {
+    tokio::process::Command::new(command)
+        .args(args)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+async fn handle_doctor(synthesize: bool, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let mut checks: Vec<DoctorCheck> = Vec::new();
+
+    // Configuration: missing file is fine (defaults apply), an invalid
+    // one is not
+    let config = match ConfigManager::find_config_file() {
+        Some(path) => match TTSConfig::from_json_file(&path) {
+            Ok(config) => {
+                let warnings = config.diagnostics();
+                checks.push(DoctorCheck {
+                    name: "config",
+                    ok: true,
+                    detail: if warnings.is_empty() {
+                        format!("valid ({})", path)
+                    } else {
+                        format!("valid ({}), {} warning(s)", path, warnings.len())
+                    },
+                    hint: None,
+                });
+                config
+            }
+            Err(e) => {
+                checks.push(DoctorCheck {
+                    name: "config",
+                    ok: false,
+                    detail: format!("{}: {}", path, e),
+                    hint: Some("fix the file or regenerate it with 'config init --force'"),
+                });
+                TTSConfig::default()
+            }
+        },
+        None => {
+            checks.push(DoctorCheck {
+                name: "config",
+                ok: true,
+                detail: "no config file, using defaults".to_string(),
+                hint: None,
+            });
+            TTSConfig::default()
+        }
+    };
+
+    // Voice catalog: exercises the endpoint and the embedded trusted
+    // client token in one request
+    let mut client = TTSClient::new(Some(config));
+    match client.list_voices().await {
+        Ok(voices) => checks.push(DoctorCheck {
+            name: "voice endpoint",
+            ok: true,
+            detail: format!("reachable, {} voices", voices.len()),
+            hint: None,
+        }),
+        Err(e) => checks.push(DoctorCheck {
+            name: "voice endpoint",
+            ok: false,
+            detail: e.to_string(),
+            hint: Some("check your network connection and proxy settings"),
+        }),
+    }
+
+    // Synthesis backends: the edge-tts binary, with python -m edge_tts as
+    // the fallback the client tries second
+    let edge_tts = command_succeeds("edge-tts", &["--version"]).await;
+    let python_fallback = command_succeeds("python", &["-m", "edge_tts", "--version"]).await;
+    checks.push(DoctorCheck {
+        name: "edge-tts backend",
+        ok: edge_tts || python_fallback,
+        detail: match (edge_tts, python_fallback) {
+            (true, true) => "edge-tts found, python fallback found".to_string(),
+            (true, false) => "edge-tts found, no python fallback".to_string(),
+            (false, true) => "edge-tts missing, python fallback found".to_string(),
+            (false, false) => "neither edge-tts nor python -m edge_tts found".to_string(),
+        },
+        hint: (!edge_tts && !python_fallback).then_some("install it with 'pip install edge-tts'"),
+    });
+
+    // Audio device: the null backend means synthesis still works but
+    // nothing will be audible
+    match AudioPlayer::new() {
+        Ok(player) if player.is_null() => checks.push(DoctorCheck {
+            name: "audio device",
+            ok: true,
+            detail: "none found, playback will be silent".to_string(),
+            hint: Some("saving to files still works; check your sound server for playback"),
+        }),
+        Ok(_) => checks.push(DoctorCheck {
+            name: "audio device",
+            ok: true,
+            detail: format!(
+                "available (backends: {})",
+                AudioPlayer::available_backends().join(", ")
+            ),
+            hint: None,
+        }),
+        Err(e) => checks.push(DoctorCheck {
+            name: "audio device",
+            ok: false,
+            detail: e.to_string(),
+            hint: Some("saving to files still works; check your sound server for playback"),
+        }),
+    }
+
+    // Optional round-trip through the whole pipeline
+    if synthesize {
+        match client.synthesize_text("Hello", "en-US-AriaNeural", None).await {
+            Ok(audio_data) => {
+                let duration = hello_edge_tts::audio_processing::probe(&audio_data)
+                    .map(|info| info.duration)
+                    .unwrap_or_default();
+                checks.push(DoctorCheck {
+                    name: "synthesis round-trip",
+                    ok: true,
+                    detail: format!("{} bytes, {:.1}s of audio", audio_data.len(), duration.as_secs_f64()),
+                    hint: None,
+                });
+            }
+            Err(e) => checks.push(DoctorCheck {
+                name: "synthesis round-trip",
+                ok: false,
+                detail: e.to_string(),
+                hint: Some("run with --verbose for the underlying edge-tts output"),
+            }),
+        }
+    }
+
+    let failed = checks.iter().filter(|check| !check.ok).count();
+
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({
+                "status": if failed == 0 { "ok" } else { "error" },
+                "failed": failed,
+                "checks": checks.iter().map(|check| serde_json::json!({
+                    "name": check.name,
+                    "ok": check.ok,
+                    "detail": check.detail,
+                    "hint": check.hint,
+                })).collect::<Vec<_>>(),
+            })
+        );
+    } else {
+        println!("🩺 hello-edge-tts doctor");
+        for check in &checks {
+            let mark = if check.ok { "✅" } else { "❌" };
+            println!("{} {}: {}", mark, check.name, check.detail);
+            if let Some(hint) = check.hint {
+                println!("   💡 {}", hint);
+            }
+        }
+        println!();
+        if failed == 0 {
+            println!("✅ All {} check(s) passed", checks.len());
+        }
+    }
+
+    if failed > 0 {
+        return Err(format!("{} of {} check(s) failed", failed, checks.len()).into());
+    }
+    Ok(())
+}
+
+/// Language/text/voice table driving the demo, embedded so the command
+/// works from any directory; `--config` overrides it
+const MULTILINGUAL_DEMO_CONFIG: &str = include_str!("multilingual_demo_config.json");
+
+/// One language of the multilingual demo table
+#[derive(serde::Deserialize)]
+struct DemoLanguage {
+    code: String,
+    name: String,
+    flag: String,
+    text: String,
+    voice: String,
+    #[serde(default)]
+    alt_voice: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+struct DemoLanguageTable {
+    languages: Vec<DemoLanguage>,
+}
+
+/// Load the demo table from an override file, or the embedded default
+fn load_demo_languages(
+    path: Option<&std::path::Path>,
+) -> Result<Vec<DemoLanguage>, Box<dyn std::error::Error>> {
+    let content = match path {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => MULTILINGUAL_DEMO_CONFIG.to_string(),
+    };
+    let table: DemoLanguageTable = serde_json::from_str(&content)?;
+    if table.languages.is_empty() {
+        return Err("Demo language table is empty".into());
+    }
+    Ok(table.languages)
+}
+
+/// Synthesize every language in the demo table, saving one file each
+async fn run_all_languages_demo(
+    languages: &[DemoLanguage],
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🌍 Running the demo for {} language(s)", languages.len());
+    println!("{}", "=".repeat(40));
+
+    let config = load_config(None).unwrap_or_default();
+    let client = TTSClient::new(Some(config.clone()));
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs();
+
+    let mut succeeded = 0usize;
+    let mut failed = 0usize;
+    for entry in languages {
+        println!("\n{} {} ({})", entry.flag, entry.name, entry.code);
+        println!("Text: {}", entry.text);
+        // Fall back to the alternative voice so one retired voice does
+        // not sink the whole tour
+        let audio_data = match client.synthesize_text(&entry.text, &entry.voice, None).await {
+            Ok(audio_data) => Ok((audio_data, entry.voice.as_str())),
+            Err(e) => match &entry.alt_voice {
+                Some(alt) => client
+                    .synthesize_text(&entry.text, alt, None)
+                    .await
+                    .map(|audio_data| (audio_data, alt.as_str())),
+                None => Err(e),
+            },
+        };
+        match audio_data {
+            Ok((audio_data, voice)) => {
+                let prefix = entry.code.split('-').next().unwrap_or("unknown");
+                let path = config
+                    .resolve_output_path(&format!("multilingual_{}_rust_{}", prefix, timestamp));
+                client.save_audio(&audio_data, path.to_str().unwrap()).await?;
+                record_history(voice, &entry.text, &audio_data, &path.display().to_string());
+                println!("✅ {} -> {}", voice, path.display());
+                succeeded += 1;
+            }
+            Err(e) => {
+                println!("❌ {}: {}", entry.voice, e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!("\n🏁 {} succeeded, {} failed", succeeded, failed);
+    if succeeded == 0 {
+        return Err("All demo languages failed to synthesize".into());
+    }
+    Ok(())
+}
+
+async fn handle_demo(
+    language: String,
+    parallel: Option<usize>,
+    all_languages: bool,
+    demo_config: Option<PathBuf>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let languages = load_demo_languages(demo_config.as_deref())?;
+    if all_languages {
+        return run_all_languages_demo(&languages).await;
+    }
+
     println!("🚀 Running Hello Edge TTS Demo");
     println!("Language: {}", language);
     println!("{}", "=".repeat(40));
@@ -247,30 +3544,31 @@ async fn handle_demo(language: String) -> Result<(), Box<dyn std::error::Error>>
             first_voice.display_name
         );
 
-        let demo_texts = match language.as_str() {
-            "en" => vec!["Hello, World!", "Welcome to Edge TTS with Rust!"],
-            "es" => vec!["¡Hola, Mundo!", "¡Bienvenido a Edge TTS con Rust!"],
-            "fr" => vec!["Bonjour, le Monde!", "Bienvenue à Edge TTS avec Rust!"],
-            "de" => vec!["Hallo, Welt!", "Willkommen bei Edge TTS mit Rust!"],
-            "ja" => vec!["こんにちは、世界！", "RustでEdge TTSへようこそ！"],
-            "zh" => vec!["你好，世界！", "欢迎使用Rust的Edge TTS！"],
-            _ => vec!["Hello, World!", "Welcome to Edge TTS with Rust!"],
-        };
+        // Sample sentences come from the embedded multilingual table,
+        // falling back to English for languages it does not cover
+        let demo_texts: Vec<&str> = languages
+            .iter()
+            .find(|entry| entry.code.starts_with(&language.to_lowercase()))
+            .map(|entry| vec![entry.text.as_str()])
+            .unwrap_or_else(|| vec!["Hello, World!", "Welcome to Edge TTS with Rust!"]);
 
         for (i, text) in demo_texts.iter().enumerate() {
             println!("   📝 Text {}: {}", i + 1, text);
+        }
 
-            match client.synthesize_text(text, &first_voice.name, None).await {
-                Ok(_audio_data) => {
-                    println!("   ✅ Synthesis successful (demo mode)");
-                }
-                Err(e) => {
-                    println!("   ❌ Synthesis failed: {}", e);
-                    println!(
-                        "   💡 This is expected in demo mode - WebSocket implementation needed"
-                    );
-                }
+        let parallel = parallel.unwrap_or_else(|| TTSConfig::default().max_concurrent);
+        match client
+            .batch_synthesize_concurrent(&demo_texts, &first_voice.name, false, parallel)
+            .await
+        {
+            Ok(results) => {
+                println!(
+                    "   ✅ Synthesized {} item(s) with up to {} in flight",
+                    results.len(),
+                    parallel.max(1)
+                );
             }
+            Err(e) => println!("   ❌ Synthesis failed: {}", e),
         }
     }
 
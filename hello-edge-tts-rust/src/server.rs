@@ -0,0 +1,552 @@
+//! Optional REST front-end for the TTS client, enabled with the `server`
+//! feature.
+//!
+//! Exposes the same operations the CLI offers locally so other
+//! applications can reuse one synthesis setup over HTTP:
+//!
+//! - `GET /health` — liveness, version, and uptime
+//! - `GET /voices` — the voice catalog as JSON, cached after the first hit
+//! - `POST /synthesize` — `{"text", "voice", "ssml"}` in, audio out; plain
+//!   text streams chunk by chunk so playback can start before synthesis
+//!   finishes
+//! - `GET /stream` — WebSocket: one `{"text", "voice"}` text frame in,
+//!   then JSON events and binary audio chunks out as they are synthesized
+//! - `GET /metrics` — request counts, latencies, cache hit rate, errors
+//!   by category, and in-flight syntheses in Prometheus text format
+//!
+//! When `server_api_keys` is configured, the synthesis routes require a
+//! `Bearer` token (or `x-api-key` header) and enforce each key's
+//! per-minute quota; `/health` and `/metrics` stay open.
+
+use axum::body::Body;
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+use crate::auth::{ApiKeyGuard, AuthError};
+use crate::ssml_utils::SSMLValidator;
+use crate::tts_client::{TTSClient, TTSConfig, TTSError};
+
+/// Shared state behind the routes: one client for the whole server so the
+/// voice cache and connection pool are reused across requests
+pub struct ServerState {
+    client: Mutex<TTSClient>,
+    config: TTSConfig,
+    started: Instant,
+    metrics: ServerMetrics,
+    auth: ApiKeyGuard,
+}
+
+/// Counters behind `GET /metrics`, all lock-free so recording never slows
+/// a request down
+#[derive(Default)]
+pub struct ServerMetrics {
+    requests: [RouteMetrics; ROUTES.len()],
+    errors_network: AtomicU64,
+    errors_not_found: AtomicU64,
+    errors_invalid: AtomicU64,
+    errors_internal: AtomicU64,
+    errors_unauthorized: AtomicU64,
+    errors_rate_limited: AtomicU64,
+    cache_hits: AtomicU64,
+    cache_misses: AtomicU64,
+    in_flight: AtomicU64,
+    catalog_warm: AtomicBool,
+}
+
+/// Route labels, indexed in lockstep with `ServerMetrics::requests`
+const ROUTES: [&str; 4] = ["health", "voices", "synthesize", "stream"];
+
+#[derive(Default)]
+struct RouteMetrics {
+    count: AtomicU64,
+    duration_micros: AtomicU64,
+}
+
+impl ServerMetrics {
+    /// Record one finished request on `route` (an index into [`ROUTES`])
+    fn observe(&self, route: usize, started: Instant) {
+        self.requests[route].count.fetch_add(1, Ordering::Relaxed);
+        self.requests[route]
+            .duration_micros
+            .fetch_add(started.elapsed().as_micros() as u64, Ordering::Relaxed);
+    }
+
+    /// Record an error under the bucket matching its HTTP status
+    fn record_error(&self, status: StatusCode) {
+        let counter = match status {
+            StatusCode::BAD_GATEWAY => &self.errors_network,
+            StatusCode::NOT_FOUND => &self.errors_not_found,
+            StatusCode::BAD_REQUEST => &self.errors_invalid,
+            StatusCode::UNAUTHORIZED => &self.errors_unauthorized,
+            StatusCode::TOO_MANY_REQUESTS => &self.errors_rate_limited,
+            _ => &self.errors_internal,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record whether the voice catalog came from the warm in-process
+    /// cache; the first lookup always goes to the service
+    fn record_catalog_lookup(&self) {
+        if self.catalog_warm.swap(true, Ordering::Relaxed) {
+            self.cache_hits.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.cache_misses.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Render the Prometheus text exposition format
+    fn render(&self, uptime_secs: u64) -> String {
+        use std::fmt::Write;
+        let mut out = String::new();
+        let _ = writeln!(out, "# HELP tts_requests_total Requests served, by route.");
+        let _ = writeln!(out, "# TYPE tts_requests_total counter");
+        for (i, route) in ROUTES.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "tts_requests_total{{route=\"{}\"}} {}",
+                route,
+                self.requests[i].count.load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP tts_request_duration_seconds Cumulative request wall time, by route."
+        );
+        let _ = writeln!(out, "# TYPE tts_request_duration_seconds counter");
+        for (i, route) in ROUTES.iter().enumerate() {
+            let _ = writeln!(
+                out,
+                "tts_request_duration_seconds{{route=\"{}\"}} {:.6}",
+                route,
+                self.requests[i].duration_micros.load(Ordering::Relaxed) as f64 / 1e6
+            );
+        }
+        let _ = writeln!(out, "# HELP tts_errors_total Failed requests, by category.");
+        let _ = writeln!(out, "# TYPE tts_errors_total counter");
+        for (category, counter) in [
+            ("network", &self.errors_network),
+            ("voice-not-found", &self.errors_not_found),
+            ("invalid-request", &self.errors_invalid),
+            ("internal", &self.errors_internal),
+            ("unauthorized", &self.errors_unauthorized),
+            ("rate-limited", &self.errors_rate_limited),
+        ] {
+            let _ = writeln!(
+                out,
+                "tts_errors_total{{category=\"{}\"}} {}",
+                category,
+                counter.load(Ordering::Relaxed)
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP tts_voice_cache_hits_total Voice catalog lookups answered from cache."
+        );
+        let _ = writeln!(out, "# TYPE tts_voice_cache_hits_total counter");
+        let _ = writeln!(
+            out,
+            "tts_voice_cache_hits_total {}",
+            self.cache_hits.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "# HELP tts_voice_cache_misses_total Voice catalog lookups that hit the service."
+        );
+        let _ = writeln!(out, "# TYPE tts_voice_cache_misses_total counter");
+        let _ = writeln!(
+            out,
+            "tts_voice_cache_misses_total {}",
+            self.cache_misses.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(
+            out,
+            "# HELP tts_synthesis_in_flight Syntheses currently running."
+        );
+        let _ = writeln!(out, "# TYPE tts_synthesis_in_flight gauge");
+        let _ = writeln!(
+            out,
+            "tts_synthesis_in_flight {}",
+            self.in_flight.load(Ordering::Relaxed)
+        );
+        let _ = writeln!(out, "# HELP tts_uptime_seconds Seconds since the server started.");
+        let _ = writeln!(out, "# TYPE tts_uptime_seconds gauge");
+        let _ = writeln!(out, "tts_uptime_seconds {}", uptime_secs);
+        out
+    }
+}
+
+/// Keeps the in-flight gauge honest even when a synthesis future or its
+/// streaming body is dropped mid-way (client disconnects)
+struct InFlightGuard(Arc<ServerState>);
+
+impl InFlightGuard {
+    fn enter(state: &Arc<ServerState>) -> Self {
+        state.metrics.in_flight.fetch_add(1, Ordering::Relaxed);
+        Self(Arc::clone(state))
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.metrics.in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Body of `POST /synthesize`, mirroring the CLI's serve subcommand
+#[derive(Deserialize)]
+pub struct SynthesizeRequest {
+    pub text: String,
+    #[serde(default)]
+    pub voice: Option<String>,
+    #[serde(default)]
+    pub ssml: bool,
+}
+
+/// Error envelope every route returns on failure, with the HTTP status
+/// chosen by what went wrong
+struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<TTSError> for ApiError {
+    fn from(error: TTSError) -> Self {
+        let status = match &error {
+            TTSError::Network(_) => StatusCode::BAD_GATEWAY,
+            TTSError::VoiceNotFound(_) => StatusCode::NOT_FOUND,
+            TTSError::Config(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self::new(status, error.to_string())
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response()
+    }
+}
+
+/// Build the router with all routes bound to a fresh client using `config`
+pub fn router(config: TTSConfig) -> Router {
+    let state = Arc::new(ServerState {
+        client: Mutex::new(TTSClient::new(Some(config.clone()))),
+        auth: ApiKeyGuard::new(&config.server_api_keys),
+        config,
+        started: Instant::now(),
+        metrics: ServerMetrics::default(),
+    });
+    if state.auth.is_open() {
+        tracing::warn!("No server_api_keys configured — the server is open to all callers");
+    }
+    // Liveness and scraping stay unauthenticated; everything that costs
+    // synthesis work sits behind the key check
+    let protected = Router::new()
+        .route("/voices", get(voices))
+        .route("/synthesize", post(synthesize))
+        .route("/stream", get(stream))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::clone(&state),
+            require_api_key,
+        ));
+    Router::new()
+        .route("/health", get(health))
+        .route("/metrics", get(metrics))
+        .merge(protected)
+        .with_state(state)
+}
+
+/// Bind `addr` and serve the router until the task is cancelled
+pub async fn serve(addr: SocketAddr, config: TTSConfig) -> Result<(), TTSError> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("REST server listening on http://{}", addr);
+    axum::serve(listener, router(config))
+        .await
+        .map_err(std::io::Error::other)?;
+    Ok(())
+}
+
+async fn health(State(state): State<Arc<ServerState>>) -> Json<serde_json::Value> {
+    let started = Instant::now();
+    let response = Json(serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+        "uptime_secs": state.started.elapsed().as_secs(),
+    }));
+    state.metrics.observe(0, started);
+    response
+}
+
+/// Check the request's API key against [`TTSConfig::server_api_keys`]
+/// before letting it reach a synthesis route. Accepts the key as
+/// `Authorization: Bearer <key>` or an `x-api-key` header.
+async fn require_api_key(
+    State(state): State<Arc<ServerState>>,
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> Response {
+    let verdict = {
+        let headers = request.headers();
+        let token = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| ApiKeyGuard::bearer(Some(value)))
+            .or_else(|| {
+                headers
+                    .get("x-api-key")
+                    .and_then(|value| value.to_str().ok())
+            });
+        state.auth.check(token)
+    };
+    match verdict {
+        Ok(()) => next.run(request).await,
+        Err(error @ AuthError::Unauthorized) => {
+            state.metrics.record_error(StatusCode::UNAUTHORIZED);
+            ApiError::new(StatusCode::UNAUTHORIZED, error.to_string()).into_response()
+        }
+        Err(AuthError::RateLimited {
+            name,
+            retry_after_secs,
+        }) => {
+            state.metrics.record_error(StatusCode::TOO_MANY_REQUESTS);
+            (
+                StatusCode::TOO_MANY_REQUESTS,
+                [(header::RETRY_AFTER, retry_after_secs.to_string())],
+                Json(serde_json::json!({
+                    "error": format!("Rate limit exceeded for '{}'", name),
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+async fn metrics(State(state): State<Arc<ServerState>>) -> Response {
+    let body = state
+        .metrics
+        .render(state.started.elapsed().as_secs());
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        body,
+    )
+        .into_response()
+}
+
+async fn voices(State(state): State<Arc<ServerState>>) -> Result<Response, ApiError> {
+    let started = Instant::now();
+    let result = voices_inner(&state).await;
+    state.metrics.observe(1, started);
+    if let Err(e) = &result {
+        state.metrics.record_error(e.status);
+    }
+    result
+}
+
+async fn voices_inner(state: &Arc<ServerState>) -> Result<Response, ApiError> {
+    state.metrics.record_catalog_lookup();
+    let voices = state.client.lock().await.list_voices().await?;
+    Ok(Json(voices).into_response())
+}
+
+async fn synthesize(
+    State(state): State<Arc<ServerState>>,
+    Json(request): Json<SynthesizeRequest>,
+) -> Result<Response, ApiError> {
+    let started = Instant::now();
+    let result = synthesize_inner(&state, request).await;
+    // For streaming bodies this observes time to the response head; the
+    // in-flight gauge covers the tail
+    state.metrics.observe(2, started);
+    if let Err(e) = &result {
+        state.metrics.record_error(e.status);
+    }
+    result
+}
+
+async fn synthesize_inner(
+    state: &Arc<ServerState>,
+    request: SynthesizeRequest,
+) -> Result<Response, ApiError> {
+    let voice = state
+        .config
+        .resolve_voice(request.voice.as_deref().unwrap_or(&state.config.default_voice));
+    {
+        state.metrics.record_catalog_lookup();
+        let mut client = state.client.lock().await;
+        let catalog = client.list_voices().await?;
+        if !catalog.iter().any(|v| v.name == voice) {
+            return Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                format!("Voice '{}' not found", voice),
+            ));
+        }
+    }
+
+    if request.ssml {
+        let problems = SSMLValidator::validate(&request.text);
+        if !problems.is_empty() {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid SSML: {}", problems.join("; ")),
+            ));
+        }
+        let _guard = InFlightGuard::enter(state);
+        let client = state.client.lock().await;
+        let audio_data = client.synthesize_ssml(&request.text, &voice).await?;
+        return Ok(([(header::CONTENT_TYPE, "audio/mpeg")], audio_data).into_response());
+    }
+
+    // Plain text streams: the body carries each chunk as soon as it is
+    // synthesized, so long inputs start playing early on the client side
+    let stream = {
+        let client = state.client.lock().await;
+        client.synthesize_stream(&request.text, &voice)
+    };
+    let guard = InFlightGuard::enter(state);
+    let body = Body::from_stream(stream.map(move |item| {
+        let _ = &guard;
+        item.map_err(std::io::Error::other)
+    }));
+    Ok(([(header::CONTENT_TYPE, "audio/mpeg")], body).into_response())
+}
+
+async fn stream(
+    State(state): State<Arc<ServerState>>,
+    upgrade: axum::extract::ws::WebSocketUpgrade,
+) -> Response {
+    upgrade.on_upgrade(move |socket| stream_session(state, socket))
+}
+
+/// One WebSocket synthesis session.
+///
+/// The first text frame must be a [`SynthesizeRequest`]; the reply is a
+/// `start` event, a `boundaries` event with estimated word timings for
+/// live highlighting, one binary frame per audio chunk, and an `end` (or
+/// `error`) event. Send errors just end the session — the peer is gone.
+async fn stream_session(state: Arc<ServerState>, socket: axum::extract::ws::WebSocket) {
+    let started = Instant::now();
+    stream_session_inner(&state, socket).await;
+    state.metrics.observe(3, started);
+}
+
+async fn stream_session_inner(state: &Arc<ServerState>, mut socket: axum::extract::ws::WebSocket) {
+    use axum::extract::ws::Message;
+
+    let event = |value: serde_json::Value| Message::Text(value.to_string());
+
+    let request: SynthesizeRequest = loop {
+        match socket.recv().await {
+            Some(Ok(Message::Text(text))) => match serde_json::from_str(&text) {
+                Ok(request) => break request,
+                Err(e) => {
+                    state.metrics.record_error(StatusCode::BAD_REQUEST);
+                    let _ = socket
+                        .send(event(serde_json::json!({
+                            "event": "error",
+                            "message": format!("Invalid request: {}", e),
+                        })))
+                        .await;
+                    return;
+                }
+            },
+            // Pings are answered by axum; skip anything else before the
+            // request arrives
+            Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return,
+            Some(Ok(_)) => continue,
+        }
+    };
+
+    let voice = state
+        .config
+        .resolve_voice(request.voice.as_deref().unwrap_or(&state.config.default_voice));
+
+    if socket
+        .send(event(serde_json::json!({ "event": "start", "voice": voice })))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    // Estimated timings, like the subtitle writers use, so web clients
+    // can highlight words while the audio plays
+    let boundaries = crate::ssml_utils::estimate_word_boundaries(&request.text);
+    let words: Vec<serde_json::Value> = boundaries
+        .iter()
+        .map(|word| {
+            serde_json::json!({
+                "text": word.text,
+                "offset_ms": word.offset.as_millis(),
+                "duration_ms": word.duration.as_millis(),
+            })
+        })
+        .collect();
+    if socket
+        .send(event(
+            serde_json::json!({ "event": "boundaries", "words": words }),
+        ))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    let chunks = {
+        let client = state.client.lock().await;
+        client.synthesize_stream(&request.text, &voice)
+    };
+    futures_util::pin_mut!(chunks);
+
+    let _guard = InFlightGuard::enter(state);
+    let mut bytes = 0usize;
+    while let Some(item) = chunks.next().await {
+        match item {
+            Ok(chunk) => {
+                bytes += chunk.len();
+                if socket.send(Message::Binary(chunk.to_vec())).await.is_err() {
+                    return;
+                }
+            }
+            Err(e) => {
+                state.metrics.record_error(StatusCode::INTERNAL_SERVER_ERROR);
+                let _ = socket
+                    .send(event(serde_json::json!({
+                        "event": "error",
+                        "message": e.to_string(),
+                    })))
+                    .await;
+                return;
+            }
+        }
+    }
+
+    let _ = socket
+        .send(event(
+            serde_json::json!({ "event": "end", "bytes": bytes }),
+        ))
+        .await;
+    let _ = socket.close().await;
+}
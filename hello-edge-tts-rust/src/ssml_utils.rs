@@ -3,6 +3,32 @@
 //! This module provides builder patterns and validation for creating
 //! SSML markup for use with Microsoft Edge TTS service.
 
+/// Relative pause strength for `<break>` elements, mirroring the values the
+/// validator accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakStrength {
+    None,
+    XWeak,
+    Weak,
+    Medium,
+    Strong,
+    XStrong,
+}
+
+impl BreakStrength {
+    /// The attribute value the strength serializes to
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BreakStrength::None => "none",
+            BreakStrength::XWeak => "x-weak",
+            BreakStrength::Weak => "weak",
+            BreakStrength::Medium => "medium",
+            BreakStrength::Strong => "strong",
+            BreakStrength::XStrong => "x-strong",
+        }
+    }
+}
+
 /// Builder for creating SSML markup
 pub struct SSMLBuilder {
     voice: String,
@@ -89,6 +115,24 @@ impl SSMLBuilder {
         self
     }
 
+    /// Add a break with a relative strength instead of an explicit time
+    pub fn add_break_strength(self, strength: BreakStrength) -> Self {
+        self.add_break_with(None, Some(strength))
+    }
+
+    /// Add a break with any combination of explicit time and strength
+    pub fn add_break_with(mut self, time: Option<&str>, strength: Option<BreakStrength>) -> Self {
+        let mut attrs = String::new();
+        if let Some(time) = time {
+            attrs.push_str(&format!(" time=\"{}\"", time));
+        }
+        if let Some(strength) = strength {
+            attrs.push_str(&format!(" strength=\"{}\"", strength.as_str()));
+        }
+        self.elements.push(format!("<break{}/>", attrs));
+        self
+    }
+
     /// Add say-as element for special text interpretation
     pub fn add_say_as(mut self, text: &str, interpret_as: &str, format: Option<&str>) -> Self {
         let format_attr = format
@@ -101,6 +145,41 @@ impl SSMLBuilder {
         self
     }
 
+    /// Add a date read out per `format` (e.g., "mdy", "dmy", "ymd")
+    pub fn add_date(self, date: &str, format: &str) -> Self {
+        self.add_say_as(date, "date", Some(format))
+    }
+
+    /// Add a time of day (e.g., "2:30pm" with format "hms12")
+    pub fn add_time(self, time: &str, format: &str) -> Self {
+        self.add_say_as(time, "time", Some(format))
+    }
+
+    /// Add a monetary amount read as currency (e.g., "$42.50")
+    pub fn add_currency(self, amount: &str) -> Self {
+        self.add_say_as(amount, "currency", None)
+    }
+
+    /// Add a telephone number read digit by digit with grouping pauses
+    pub fn add_telephone(self, number: &str) -> Self {
+        self.add_say_as(number, "telephone", None)
+    }
+
+    /// Add text spelled out letter by letter (e.g., acronyms or codes)
+    pub fn add_spell_out(self, text: &str) -> Self {
+        self.add_say_as(text, "characters", None)
+    }
+
+    /// Add a number read as an ordinal ("3" becomes "third")
+    pub fn add_ordinal(self, number: &str) -> Self {
+        self.add_say_as(number, "ordinal", None)
+    }
+
+    /// Add a fraction read naturally ("3/8" becomes "three eighths")
+    pub fn add_fraction(self, fraction: &str) -> Self {
+        self.add_say_as(fraction, "fraction", None)
+    }
+
     /// Add phoneme pronunciation
     pub fn add_phoneme(mut self, text: &str, alphabet: &str, ph: &str) -> Self {
         self.elements.push(format!(
@@ -110,6 +189,81 @@ impl SSMLBuilder {
         self
     }
 
+    /// Add a prosody element whose nested content is composed with the
+    /// closure-based scope API:
+    ///
+    /// ```
+    /// # use hello_edge_tts::SSMLBuilder;
+    /// let ssml = SSMLBuilder::new("en-US-AriaNeural")
+    ///     .prosody(Some("slow"), None, None, |b| {
+    ///         b.text("so ").emphasis("strong", |b| b.text("very")).text(" calm")
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn prosody(
+        mut self,
+        rate: Option<&str>,
+        pitch: Option<&str>,
+        volume: Option<&str>,
+        f: impl FnOnce(SSMLScope) -> SSMLScope,
+    ) -> Self {
+        let scope = SSMLScope::default().prosody(rate, pitch, volume, f);
+        self.elements.push(scope.content);
+        self
+    }
+
+    /// Add an emphasis element with nested scope content
+    pub fn emphasis(mut self, level: &str, f: impl FnOnce(SSMLScope) -> SSMLScope) -> Self {
+        let scope = SSMLScope::default().emphasis(level, f);
+        self.elements.push(scope.content);
+        self
+    }
+
+    /// Add a lang element with nested scope content
+    pub fn lang(mut self, lang: &str, f: impl FnOnce(SSMLScope) -> SSMLScope) -> Self {
+        let scope = SSMLScope::default().lang(lang, f);
+        self.elements.push(scope.content);
+        self
+    }
+
+    /// Add a sentence wrapped in `<s>` so the engine paces it explicitly
+    /// instead of guessing the boundary
+    pub fn add_sentence(mut self, text: &str) -> Self {
+        self.elements.push(format!("<s>{}</s>", text));
+        self
+    }
+
+    /// Add a paragraph of sentences wrapped in `<p>`, each sentence in its
+    /// own `<s>` element
+    pub fn add_paragraph(mut self, sentences: &[&str]) -> Self {
+        let body: String = sentences
+            .iter()
+            .map(|s| format!("<s>{}</s>", s))
+            .collect();
+        self.elements.push(format!("<p>{}</p>", body));
+        self
+    }
+
+    /// Add IPA phoneme pronunciation
+    pub fn add_phoneme_ipa(self, text: &str, ph: &str) -> Self {
+        self.add_phoneme(text, "ipa", ph)
+    }
+
+    /// Add SAPI phoneme pronunciation; use [`sapi_to_ipa`] first to verify
+    /// the phone tokens are valid
+    pub fn add_phoneme_sapi(self, text: &str, ph: &str) -> Self {
+        self.add_phoneme(text, "sapi", ph)
+    }
+
+    /// Add a word from the built-in tricky-word pronunciation table, or as
+    /// plain text when the word has no entry
+    pub fn add_tricky_word(self, word: &str) -> Self {
+        match builtin_phoneme(word) {
+            Some(ph) => self.add_phoneme(word, "ipa", ph),
+            None => self.add_text(word),
+        }
+    }
+
     /// Add substitution
     pub fn add_sub(mut self, text: &str, alias: &str) -> Self {
         self.elements
@@ -117,11 +271,94 @@ impl SSMLBuilder {
         self
     }
 
+    /// Reference an external PLS pronunciation lexicon by URI. The lexicon
+    /// reference must precede the content it applies to, so it is inserted
+    /// at the front of the document.
+    pub fn add_lexicon(mut self, uri: &str) -> Self {
+        self.elements
+            .insert(0, format!("<lexicon uri=\"{}\"/>", uri));
+        self
+    }
+
+    /// Add text spoken in a different language than the surrounding speech,
+    /// so mixed-language sentences are pronounced correctly without
+    /// splitting them into multiple requests
+    pub fn add_lang(mut self, lang: &str, text: &str) -> Self {
+        self.elements
+            .push(format!("<lang xml:lang=\"{}\">{}</lang>", lang, text));
+        self
+    }
+
+    /// Control how much silence the engine inserts at a given position
+    /// (e.g., `add_silence("Sentenceboundary", "200ms")`). Valid types
+    /// include "Leading", "Tailing", and "Sentenceboundary", each with an
+    /// "-exact" variant that replaces the natural pause instead of capping it.
+    pub fn add_silence(mut self, silence_type: &str, value: &str) -> Self {
+        self.elements.push(format!(
+            "<mstts:silence type=\"{}\" value=\"{}\"/>",
+            silence_type, value
+        ));
+        self
+    }
+
+    /// Add a bookmark marker; the synthesis stream reports when playback
+    /// reaches it so applications can trigger actions at that point
+    pub fn add_bookmark(mut self, name: &str) -> Self {
+        self.elements
+            .push(format!("<bookmark mark=\"{}\"/>", name));
+        self
+    }
+
+    /// Add a pre-recorded audio clip (e.g., an earcon or jingle) played
+    /// inline; `fallback_text` is spoken if the clip cannot be fetched
+    pub fn add_audio(mut self, url: &str, fallback_text: &str) -> Self {
+        if fallback_text.is_empty() {
+            self.elements.push(format!("<audio src=\"{}\"/>", url));
+        } else {
+            self.elements.push(format!(
+                "<audio src=\"{}\">{}</audio>",
+                url, fallback_text
+            ));
+        }
+        self
+    }
+
+    /// Add text spoken in a speaking style (e.g., "cheerful", "newscast")
+    pub fn add_express_as(self, text: &str, style: &str) -> Self {
+        self.add_express_as_with(text, style, None, None)
+    }
+
+    /// Add text spoken in a speaking style with optional intensity and role.
+    ///
+    /// `styledegree` scales the style intensity (0.01 to 2.0, 1.0 is the
+    /// default), and `role` makes the voice imitate a different age/gender
+    /// (e.g., "OlderAdultFemale" for a narrator).
+    pub fn add_express_as_with(
+        mut self,
+        text: &str,
+        style: &str,
+        styledegree: Option<f32>,
+        role: Option<&str>,
+    ) -> Self {
+        let mut attrs = format!("style=\"{}\"", style);
+        if let Some(degree) = styledegree {
+            attrs.push_str(&format!(" styledegree=\"{}\"", degree));
+        }
+        if let Some(role) = role {
+            attrs.push_str(&format!(" role=\"{}\"", role));
+        }
+        self.elements.push(format!(
+            "<mstts:express-as {}>{}</mstts:express-as>",
+            attrs, text
+        ));
+        self
+    }
+
     /// Build the complete SSML markup
     pub fn build(self) -> String {
         let content = self.elements.join("");
         format!(
-            r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="{}">
+            r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="https://www.w3.org/2001/mstts" xml:lang="{}">
     <voice name="{}">
         {}
     </voice>
@@ -131,6 +368,64 @@ impl SSMLBuilder {
     }
 }
 
+/// Content scope used by the closure-based nesting API, allowing valid
+/// nested SSML (emphasis inside prosody inside a lang block) that the flat
+/// element list cannot express. See [`SSMLBuilder::prosody`].
+#[derive(Debug, Default)]
+pub struct SSMLScope {
+    content: String,
+}
+
+impl SSMLScope {
+    /// Add plain text, XML-escaped
+    pub fn text(mut self, text: &str) -> Self {
+        self.content.push_str(&escape_text(text));
+        self
+    }
+
+    /// Add a break/pause
+    pub fn pause(mut self, time: &str) -> Self {
+        self.content.push_str(&format!("<break time=\"{}\"/>", time));
+        self
+    }
+
+    /// Nest content inside an emphasis element
+    pub fn emphasis(mut self, level: &str, f: impl FnOnce(SSMLScope) -> SSMLScope) -> Self {
+        let inner = f(SSMLScope::default()).content;
+        self.content
+            .push_str(&format!("<emphasis level=\"{}\">{}</emphasis>", level, inner));
+        self
+    }
+
+    /// Nest content inside a prosody element
+    pub fn prosody(
+        mut self,
+        rate: Option<&str>,
+        pitch: Option<&str>,
+        volume: Option<&str>,
+        f: impl FnOnce(SSMLScope) -> SSMLScope,
+    ) -> Self {
+        let mut attrs = String::new();
+        for (key, value) in [("rate", rate), ("pitch", pitch), ("volume", volume)] {
+            if let Some(value) = value {
+                attrs.push_str(&format!(" {}=\"{}\"", key, value));
+            }
+        }
+        let inner = f(SSMLScope::default()).content;
+        self.content
+            .push_str(&format!("<prosody{}>{}</prosody>", attrs, inner));
+        self
+    }
+
+    /// Nest content inside a lang element
+    pub fn lang(mut self, lang: &str, f: impl FnOnce(SSMLScope) -> SSMLScope) -> Self {
+        let inner = f(SSMLScope::default()).content;
+        self.content
+            .push_str(&format!("<lang xml:lang=\"{}\">{}</lang>", lang, inner));
+        self
+    }
+}
+
 /// Validator for SSML markup
 pub struct SSMLValidator;
 
@@ -149,108 +444,666 @@ impl SSMLValidator {
     const VALID_BREAK_STRENGTHS: &'static [&'static str] =
         &["none", "x-weak", "weak", "medium", "strong", "x-strong"];
 
-    /// Validate SSML markup and return list of errors
+    const VALID_SILENCE_TYPES: &'static [&'static str] = &[
+        "Leading",
+        "Leading-exact",
+        "Tailing",
+        "Tailing-exact",
+        "Sentenceboundary",
+        "Sentenceboundary-exact",
+        "Comma-exact",
+        "Semicolon-exact",
+        "Enumerationcomma-exact",
+    ];
+
+    const VALID_EXPRESS_AS_ROLES: &'static [&'static str] = &[
+        "Girl",
+        "Boy",
+        "YoungAdultFemale",
+        "YoungAdultMale",
+        "OlderAdultFemale",
+        "OlderAdultMale",
+        "SeniorFemale",
+        "SeniorMale",
+    ];
+
+    /// Validate SSML markup and return list of errors.
+    ///
+    /// The document is parsed with a real XML parser, so malformed nesting,
+    /// unclosed tags, and bad entities are reported with their line and
+    /// column in addition to the Edge TTS attribute checks.
     pub fn validate(ssml: &str) -> Vec<String> {
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+
         let mut errors = Vec::new();
+        let mut reader = Reader::from_str(ssml);
+        reader.trim_text(false);
+
+        let mut open_elements: Vec<String> = Vec::new();
+        let mut saw_root = false;
+
+        loop {
+            let position = reader.buffer_position();
+            match reader.read_event() {
+                Ok(Event::Start(element)) => {
+                    let name = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                    if open_elements.is_empty() {
+                        saw_root = true;
+                        Self::validate_speak_element(&element, ssml, position, &mut errors);
+                        if name != "speak" {
+                            errors.push(Self::positioned(
+                                ssml,
+                                position,
+                                &format!("Root element must be <speak>, found <{}>", name),
+                            ));
+                        }
+                    }
+                    Self::validate_element(&name, &element, ssml, position, &mut errors);
+                    open_elements.push(name);
+                }
+                Ok(Event::Empty(element)) => {
+                    let name = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                    if open_elements.is_empty() && !saw_root {
+                        saw_root = true;
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Root element must be <speak>, found <{}>", name),
+                        ));
+                    }
+                    Self::validate_element(&name, &element, ssml, position, &mut errors);
+                }
+                Ok(Event::End(_)) => {
+                    open_elements.pop();
+                }
+                Ok(Event::Text(text)) => {
+                    if let Err(e) = text.unescape() {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("XML error: {}", e),
+                        ));
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => {
+                    errors.push(Self::positioned(ssml, position, &format!("XML error: {}", e)));
+                    break;
+                }
+            }
+        }
 
-        // Basic validation
-        if !ssml.trim_start().starts_with("<speak") {
+        if !saw_root {
             errors.push("SSML must start with <speak> element".to_string());
         }
 
-        if !ssml.contains("version=\"1.0\"") {
-            errors.push("Missing version=\"1.0\" attribute in <speak> element".to_string());
+        errors
+    }
+
+    /// Format a message with the 1-based line and column of a byte offset
+    fn positioned(ssml: &str, offset: usize, message: &str) -> String {
+        let (line, column) = Self::line_column(ssml, offset);
+        format!("{} (line {}, column {})", message, line, column)
+    }
+
+    fn line_column(ssml: &str, offset: usize) -> (usize, usize) {
+        let upto = &ssml.as_bytes()[..offset.min(ssml.len())];
+        let line = upto.iter().filter(|&&b| b == b'\n').count() + 1;
+        let column = upto
+            .iter()
+            .rev()
+            .take_while(|&&b| b != b'\n')
+            .count()
+            + 1;
+        (line, column)
+    }
+
+    /// Check a numeric rate against the range Edge actually accepts, so
+    /// out-of-range values fail here rather than being silently clamped
+    fn check_rate_range(rate: &str) -> Option<String> {
+        let percent = rate.strip_suffix('%')?;
+        let value = percent.trim_start_matches('+').parse::<f64>().ok()?;
+        if !(-100.0..=200.0).contains(&value) {
+            return Some(format!(
+                "Prosody rate out of range (-100%..+200%): {}",
+                rate
+            ));
+        }
+        None
+    }
+
+    fn check_pitch_range(pitch: &str) -> Option<String> {
+        if let Some(hz) = pitch.strip_suffix("Hz") {
+            let relative = hz.starts_with('+') || hz.starts_with('-');
+            let value = hz.trim_start_matches('+').parse::<f64>().ok()?;
+            if relative && !(-100.0..=100.0).contains(&value) {
+                return Some(format!(
+                    "Relative prosody pitch out of range (-100Hz..+100Hz): {}",
+                    pitch
+                ));
+            }
+            if !relative && !(50.0..=600.0).contains(&value) {
+                return Some(format!(
+                    "Absolute prosody pitch out of range (50Hz..600Hz): {}",
+                    pitch
+                ));
+            }
+        } else if let Some(st) = pitch.strip_suffix("st") {
+            let value = st.trim_start_matches('+').parse::<f64>().ok()?;
+            if !(-12.0..=12.0).contains(&value) {
+                return Some(format!(
+                    "Prosody pitch out of range (-12st..+12st): {}",
+                    pitch
+                ));
+            }
         }
+        None
+    }
 
-        if !ssml.contains("xmlns=\"http://www.w3.org/2001/10/synthesis\"") {
-            errors.push("Missing xmlns attribute in <speak> element".to_string());
+    fn check_volume_range(volume: &str) -> Option<String> {
+        let db = volume.strip_suffix("dB")?;
+        let value = db.trim_start_matches('+').parse::<f64>().ok()?;
+        if !(-100.0..=10.0).contains(&value) {
+            return Some(format!(
+                "Prosody volume out of range (-100dB..+10dB): {}",
+                volume
+            ));
         }
+        None
+    }
 
-        // Validate specific elements
-        Self::validate_prosody_elements(ssml, &mut errors);
-        Self::validate_emphasis_elements(ssml, &mut errors);
-        Self::validate_break_elements(ssml, &mut errors);
+    fn attribute(element: &quick_xml::events::BytesStart<'_>, name: &str) -> Option<String> {
+        element.attributes().flatten().find_map(|attr| {
+            if attr.key.as_ref() == name.as_bytes() {
+                Some(String::from_utf8_lossy(&attr.value).to_string())
+            } else {
+                None
+            }
+        })
+    }
 
-        errors
+    fn validate_speak_element(
+        element: &quick_xml::events::BytesStart<'_>,
+        ssml: &str,
+        position: usize,
+        errors: &mut Vec<String>,
+    ) {
+        if Self::attribute(element, "version").as_deref() != Some("1.0") {
+            errors.push(Self::positioned(
+                ssml,
+                position,
+                "Missing version=\"1.0\" attribute in <speak> element",
+            ));
+        }
+        if Self::attribute(element, "xmlns").as_deref()
+            != Some("http://www.w3.org/2001/10/synthesis")
+        {
+            errors.push(Self::positioned(
+                ssml,
+                position,
+                "Missing xmlns attribute in <speak> element",
+            ));
+        }
     }
 
-    #[allow(clippy::regex_creation_in_loops)]
-    fn validate_prosody_elements(ssml: &str, errors: &mut Vec<String>) {
-        use regex::Regex;
+    fn validate_element(
+        name: &str,
+        element: &quick_xml::events::BytesStart<'_>,
+        ssml: &str,
+        position: usize,
+        errors: &mut Vec<String>,
+    ) {
+        match name {
+            "prosody" => {
+                if let Some(rate) = Self::attribute(element, "rate") {
+                    if !Self::VALID_PROSODY_RATES.contains(&rate.as_str())
+                        && !rate.ends_with('%')
+                        && !rate.ends_with("Hz")
+                    {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid prosody rate: {}", rate),
+                        ));
+                    } else if let Some(error) = Self::check_rate_range(&rate) {
+                        errors.push(Self::positioned(ssml, position, &error));
+                    }
+                }
+                if let Some(pitch) = Self::attribute(element, "pitch") {
+                    if !Self::VALID_PROSODY_PITCHES.contains(&pitch.as_str())
+                        && !pitch.ends_with("Hz")
+                        && !pitch.ends_with("st")
+                    {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid prosody pitch: {}", pitch),
+                        ));
+                    } else if let Some(error) = Self::check_pitch_range(&pitch) {
+                        errors.push(Self::positioned(ssml, position, &error));
+                    }
+                }
+                if let Some(volume) = Self::attribute(element, "volume") {
+                    if !Self::VALID_PROSODY_VOLUMES.contains(&volume.as_str())
+                        && !volume.ends_with("dB")
+                    {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid prosody volume: {}", volume),
+                        ));
+                    } else if let Some(error) = Self::check_volume_range(&volume) {
+                        errors.push(Self::positioned(ssml, position, &error));
+                    }
+                }
+            }
+            "emphasis" => {
+                if let Some(level) = Self::attribute(element, "level") {
+                    if !Self::VALID_EMPHASIS_LEVELS.contains(&level.as_str()) {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid emphasis level: {}", level),
+                        ));
+                    }
+                }
+            }
+            "break" => {
+                if let Some(time) = Self::attribute(element, "time") {
+                    if !time.ends_with('s') && !time.ends_with("ms") {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid break time format: {}", time),
+                        ));
+                    }
+                }
+                if let Some(strength) = Self::attribute(element, "strength") {
+                    if !Self::VALID_BREAK_STRENGTHS.contains(&strength.as_str()) {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid break strength: {}", strength),
+                        ));
+                    }
+                }
+            }
+            "phoneme" => {
+                if let Some(alphabet) = Self::attribute(element, "alphabet") {
+                    if !["ipa", "sapi", "ups", "x-sampa"].contains(&alphabet.as_str()) {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid phoneme alphabet: {}", alphabet),
+                        ));
+                    }
+                }
+            }
+            "mstts:silence" => {
+                match Self::attribute(element, "type") {
+                    Some(silence_type)
+                        if !Self::VALID_SILENCE_TYPES.contains(&silence_type.as_str()) =>
+                    {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid mstts:silence type: {}", silence_type),
+                        ));
+                    }
+                    Some(_) => {}
+                    None => errors.push(Self::positioned(
+                        ssml,
+                        position,
+                        "Missing type attribute in <mstts:silence> element",
+                    )),
+                }
+                match Self::attribute(element, "value") {
+                    Some(value) if !value.ends_with('s') && !value.ends_with("ms") => {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid mstts:silence value: {}", value),
+                        ));
+                    }
+                    Some(_) => {}
+                    None => errors.push(Self::positioned(
+                        ssml,
+                        position,
+                        "Missing value attribute in <mstts:silence> element",
+                    )),
+                }
+            }
+            "mstts:express-as" => {
+                if Self::attribute(element, "style").is_none() {
+                    errors.push(Self::positioned(
+                        ssml,
+                        position,
+                        "Missing style attribute in <mstts:express-as> element",
+                    ));
+                }
+                if let Some(degree) = Self::attribute(element, "styledegree") {
+                    match degree.parse::<f32>() {
+                        Ok(value) if (0.01..=2.0).contains(&value) => {}
+                        _ => errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid styledegree (must be 0.01-2.0): {}", degree),
+                        )),
+                    }
+                }
+                if let Some(role) = Self::attribute(element, "role") {
+                    if !Self::VALID_EXPRESS_AS_ROLES.contains(&role.as_str()) {
+                        errors.push(Self::positioned(
+                            ssml,
+                            position,
+                            &format!("Invalid express-as role: {}", role),
+                        ));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// A parsed SSML document: the `<speak>` root with its language and content.
+/// Obtained from [`parse`] and serialized back with [`SsmlDocument::to_ssml`],
+/// enabling programmatic manipulation instead of string surgery.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SsmlDocument {
+    pub lang: String,
+    pub children: Vec<SsmlNode>,
+}
 
-        let prosody_regex = Regex::new(r"<prosody\s+([^>]+)>").unwrap();
+/// One node of a parsed SSML document. Elements the parser has no typed
+/// representation for are preserved as [`SsmlNode::Element`] so documents
+/// round-trip without losing markup.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SsmlNode {
+    Text(String),
+    Voice {
+        name: String,
+        children: Vec<SsmlNode>,
+    },
+    Prosody {
+        rate: Option<String>,
+        pitch: Option<String>,
+        volume: Option<String>,
+        children: Vec<SsmlNode>,
+    },
+    Break {
+        time: Option<String>,
+        strength: Option<String>,
+    },
+    SayAs {
+        interpret_as: String,
+        format: Option<String>,
+        children: Vec<SsmlNode>,
+    },
+    Element {
+        name: String,
+        attributes: Vec<(String, String)>,
+        children: Vec<SsmlNode>,
+    },
+}
 
-        for caps in prosody_regex.captures_iter(ssml) {
-            let attrs = &caps[1];
+impl SsmlDocument {
+    /// Serialize the document back to SSML markup
+    pub fn to_ssml(&self) -> String {
+        let mut content = String::new();
+        for child in &self.children {
+            child.write(&mut content);
+        }
+        format!(
+            r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xmlns:mstts="https://www.w3.org/2001/mstts" xml:lang="{}">{}</speak>"#,
+            self.lang, content
+        )
+    }
+}
 
-            if let Some(rate_caps) = Regex::new(r#"rate="([^"]+)""#).unwrap().captures(attrs) {
-                let rate = &rate_caps[1];
-                if !Self::VALID_PROSODY_RATES.contains(&rate)
-                    && !rate.ends_with('%')
-                    && !rate.ends_with("Hz")
-                {
-                    errors.push(format!("Invalid prosody rate: {}", rate));
+impl SsmlNode {
+    fn write(&self, out: &mut String) {
+        match self {
+            SsmlNode::Text(text) => out.push_str(&escape_text(text)),
+            SsmlNode::Voice { name, children } => {
+                out.push_str(&format!("<voice name=\"{}\">", name));
+                for child in children {
+                    child.write(out);
                 }
+                out.push_str("</voice>");
             }
-
-            if let Some(pitch_caps) = Regex::new(r#"pitch="([^"]+)""#).unwrap().captures(attrs) {
-                let pitch = &pitch_caps[1];
-                if !Self::VALID_PROSODY_PITCHES.contains(&pitch)
-                    && !pitch.ends_with("Hz")
-                    && !pitch.ends_with("st")
-                {
-                    errors.push(format!("Invalid prosody pitch: {}", pitch));
+            SsmlNode::Prosody {
+                rate,
+                pitch,
+                volume,
+                children,
+            } => {
+                out.push_str("<prosody");
+                for (key, value) in [("rate", rate), ("pitch", pitch), ("volume", volume)] {
+                    if let Some(value) = value {
+                        out.push_str(&format!(" {}=\"{}\"", key, value));
+                    }
                 }
+                out.push('>');
+                for child in children {
+                    child.write(out);
+                }
+                out.push_str("</prosody>");
             }
-
-            if let Some(volume_caps) = Regex::new(r#"volume="([^"]+)""#).unwrap().captures(attrs) {
-                let volume = &volume_caps[1];
-                if !Self::VALID_PROSODY_VOLUMES.contains(&volume) && !volume.ends_with("dB") {
-                    errors.push(format!("Invalid prosody volume: {}", volume));
+            SsmlNode::Break { time, strength } => {
+                out.push_str("<break");
+                for (key, value) in [("time", time), ("strength", strength)] {
+                    if let Some(value) = value {
+                        out.push_str(&format!(" {}=\"{}\"", key, value));
+                    }
+                }
+                out.push_str("/>");
+            }
+            SsmlNode::SayAs {
+                interpret_as,
+                format,
+                children,
+            } => {
+                out.push_str(&format!("<say-as interpret-as=\"{}\"", interpret_as));
+                if let Some(format) = format {
+                    out.push_str(&format!(" format=\"{}\"", format));
+                }
+                out.push('>');
+                for child in children {
+                    child.write(out);
+                }
+                out.push_str("</say-as>");
+            }
+            SsmlNode::Element {
+                name,
+                attributes,
+                children,
+            } => {
+                out.push_str(&format!("<{}", name));
+                for (key, value) in attributes {
+                    out.push_str(&format!(" {}=\"{}\"", key, value));
+                }
+                if children.is_empty() {
+                    out.push_str("/>");
+                } else {
+                    out.push('>');
+                    for child in children {
+                        child.write(out);
+                    }
+                    out.push_str(&format!("</{}>", name));
                 }
             }
         }
     }
+}
+
+/// Parse SSML into a typed document tree, see [`SsmlDocument`]
+pub fn parse(ssml: &str) -> Result<SsmlDocument, String> {
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+
+    fn attr(element: &BytesStart<'_>, name: &str) -> Option<String> {
+        element.attributes().flatten().find_map(|a| {
+            if a.key.as_ref() == name.as_bytes() {
+                Some(String::from_utf8_lossy(&a.value).to_string())
+            } else {
+                None
+            }
+        })
+    }
 
-    fn validate_emphasis_elements(ssml: &str, errors: &mut Vec<String>) {
-        use regex::Regex;
+    fn all_attrs(element: &BytesStart<'_>) -> Vec<(String, String)> {
+        element
+            .attributes()
+            .flatten()
+            .map(|a| {
+                (
+                    String::from_utf8_lossy(a.key.as_ref()).to_string(),
+                    String::from_utf8_lossy(&a.value).to_string(),
+                )
+            })
+            .collect()
+    }
 
-        let emphasis_regex = Regex::new(r#"<emphasis\s+level="([^"]+)""#).unwrap();
+    // Elements awaiting their closing tag, each with the children seen so far
+    enum Pending {
+        Speak { lang: String },
+        Voice { name: String },
+        Prosody {
+            rate: Option<String>,
+            pitch: Option<String>,
+            volume: Option<String>,
+        },
+        SayAs {
+            interpret_as: String,
+            format: Option<String>,
+        },
+        Other {
+            name: String,
+            attributes: Vec<(String, String)>,
+        },
+    }
 
-        for caps in emphasis_regex.captures_iter(ssml) {
-            let level = &caps[1];
-            if !Self::VALID_EMPHASIS_LEVELS.contains(&level) {
-                errors.push(format!("Invalid emphasis level: {}", level));
-            }
+    fn open(name: &str, element: &BytesStart<'_>) -> Pending {
+        match name {
+            "speak" => Pending::Speak {
+                lang: attr(element, "xml:lang").unwrap_or_else(|| "en-US".to_string()),
+            },
+            "voice" => Pending::Voice {
+                name: attr(element, "name").unwrap_or_default(),
+            },
+            "prosody" => Pending::Prosody {
+                rate: attr(element, "rate"),
+                pitch: attr(element, "pitch"),
+                volume: attr(element, "volume"),
+            },
+            "say-as" => Pending::SayAs {
+                interpret_as: attr(element, "interpret-as").unwrap_or_default(),
+                format: attr(element, "format"),
+            },
+            _ => Pending::Other {
+                name: name.to_string(),
+                attributes: all_attrs(element),
+            },
         }
     }
 
-    #[allow(clippy::regex_creation_in_loops)]
-    fn validate_break_elements(ssml: &str, errors: &mut Vec<String>) {
-        use regex::Regex;
+    fn close(pending: Pending, children: Vec<SsmlNode>) -> SsmlNode {
+        match pending {
+            // The root is unwrapped by the caller, not emitted as a node
+            Pending::Speak { .. } => unreachable!("speak handled by caller"),
+            Pending::Voice { name } => SsmlNode::Voice { name, children },
+            Pending::Prosody { rate, pitch, volume } => SsmlNode::Prosody {
+                rate,
+                pitch,
+                volume,
+                children,
+            },
+            Pending::SayAs {
+                interpret_as,
+                format,
+            } => SsmlNode::SayAs {
+                interpret_as,
+                format,
+                children,
+            },
+            Pending::Other { name, attributes } => SsmlNode::Element {
+                name,
+                attributes,
+                children,
+            },
+        }
+    }
 
-        let break_regex = Regex::new(r"<break\s+([^>]+)/>").unwrap();
+    let mut reader = Reader::from_str(ssml);
+    reader.trim_text(false);
 
-        for caps in break_regex.captures_iter(ssml) {
-            let attrs = &caps[1];
+    let mut stack: Vec<(Pending, Vec<SsmlNode>)> = Vec::new();
+    let mut document: Option<SsmlDocument> = None;
 
-            if let Some(time_caps) = Regex::new(r#"time="([^"]+)""#).unwrap().captures(attrs) {
-                let time = &time_caps[1];
-                if !time.ends_with('s') && !time.ends_with("ms") {
-                    errors.push(format!("Invalid break time format: {}", time));
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(element)) => {
+                let name = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                if stack.is_empty() && name != "speak" {
+                    return Err(format!("Root element must be <speak>, found <{}>", name));
                 }
+                stack.push((open(&name, &element), Vec::new()));
             }
-
-            if let Some(strength_caps) =
-                Regex::new(r#"strength="([^"]+)""#).unwrap().captures(attrs)
-            {
-                let strength = &strength_caps[1];
-                if !Self::VALID_BREAK_STRENGTHS.contains(&strength) {
-                    errors.push(format!("Invalid break strength: {}", strength));
+            Ok(Event::Empty(element)) => {
+                let name = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                let node = if name == "break" {
+                    SsmlNode::Break {
+                        time: attr(&element, "time"),
+                        strength: attr(&element, "strength"),
+                    }
+                } else {
+                    SsmlNode::Element {
+                        name,
+                        attributes: all_attrs(&element),
+                        children: Vec::new(),
+                    }
+                };
+                match stack.last_mut() {
+                    Some((_, children)) => children.push(node),
+                    None => return Err("Element outside <speak> root".to_string()),
+                }
+            }
+            Ok(Event::Text(text)) => {
+                let text = text
+                    .unescape()
+                    .map_err(|e| format!("XML error: {}", e))?
+                    .to_string();
+                if !text.trim().is_empty() {
+                    match stack.last_mut() {
+                        Some((_, children)) => {
+                            children.push(SsmlNode::Text(text.trim().to_string()))
+                        }
+                        None => return Err("Text outside <speak> root".to_string()),
+                    }
+                }
+            }
+            Ok(Event::End(_)) => {
+                let (pending, children) = stack.pop().ok_or("Unbalanced closing tag")?;
+                if let Pending::Speak { lang } = pending {
+                    document = Some(SsmlDocument { lang, children });
+                } else {
+                    let node = close(pending, children);
+                    match stack.last_mut() {
+                        Some((_, children)) => children.push(node),
+                        None => return Err("Element outside <speak> root".to_string()),
+                    }
                 }
             }
+            Ok(Event::Eof) => break,
+            Ok(_) => {}
+            Err(e) => return Err(format!("XML error: {}", e)),
         }
     }
+
+    document.ok_or_else(|| "SSML must contain a <speak> element".to_string())
 }
 
 /// Predefined SSML templates
@@ -306,6 +1159,61 @@ impl SSMLTemplates {
         }
     }
 
+    /// Fill `{name}`-style placeholders in a user-defined template with
+    /// values from a substitution map. Values are XML-escaped, `{{` and `}}`
+    /// produce literal braces, and unknown or unclosed placeholders are
+    /// reported as errors.
+    pub fn fill_placeholders(
+        template: &str,
+        values: &std::collections::HashMap<String, String>,
+    ) -> Result<String, String> {
+        let mut out = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(format!("Unclosed placeholder: {{{}", name));
+                            }
+                        }
+                    }
+                    let value = values
+                        .get(name.trim())
+                        .ok_or_else(|| format!("Missing placeholder value: {}", name.trim()))?;
+                    out.push_str(&escape_text(value));
+                }
+                c => out.push(c),
+            }
+        }
+
+        Ok(out)
+    }
+
+    /// Render a user-defined announcement template (e.g., "Train {line}
+    /// departs at {time}") into complete SSML for the given voice
+    pub fn create_from_custom_template(
+        template: &str,
+        values: &std::collections::HashMap<String, String>,
+        voice: &str,
+    ) -> Result<String, String> {
+        let text = Self::fill_placeholders(template, values)?;
+        Ok(SSMLBuilder::new(voice).add_text(&text).build())
+    }
+
     /// Get list of available template names
     pub fn get_available_templates() -> Vec<&'static str> {
         vec![
@@ -349,26 +1257,1055 @@ pub fn create_emphasis_ssml(text: &str, voice: &str, level: &str) -> String {
     SSMLBuilder::new(voice).add_emphasis(text, level).build()
 }
 
-/// Create SSML with breaks between text parts
-pub fn create_break_ssml(text_parts: &[&str], voice: &str, break_time: &str) -> String {
-    let mut builder = SSMLBuilder::new(voice);
+/// say-as interpretations Edge reliably supports; others are unwrapped to
+/// plain text by the sanitizer
+const SUPPORTED_SAY_AS: &[&str] = &[
+    "cardinal",
+    "ordinal",
+    "digits",
+    "characters",
+    "spell-out",
+    "fraction",
+    "date",
+    "time",
+    "telephone",
+    "currency",
+];
+
+/// Rewrite or strip elements Edge is known to ignore or reject, returning
+/// the sanitized document and a report of what was changed:
+///
+/// - `<audio>` is not fetched by the Edge endpoint, so it is replaced by its
+///   fallback content
+/// - `<lexicon>` references are not honored and are removed (apply a
+///   [`Lexicon`] locally instead)
+/// - `<say-as>` with an unsupported interpretation is unwrapped to its text
+pub fn sanitize_ssml(ssml: &str) -> Result<(String, Vec<String>), String> {
+    let document = parse(ssml)?;
+    let mut report = Vec::new();
+    let children = sanitize_nodes(document.children, &mut report);
+    let sanitized = SsmlDocument {
+        lang: document.lang,
+        children,
+    };
+    Ok((sanitized.to_ssml(), report))
+}
 
-    for (i, part) in text_parts.iter().enumerate() {
-        builder = builder.add_text(part);
-        if i < text_parts.len() - 1 {
-            builder = builder.add_break(break_time);
+fn sanitize_nodes(nodes: Vec<SsmlNode>, report: &mut Vec<String>) -> Vec<SsmlNode> {
+    let mut out = Vec::new();
+    for node in nodes {
+        match node {
+            SsmlNode::Element {
+                name,
+                attributes,
+                children,
+            } if name == "audio" => {
+                report.push(
+                    "Replaced <audio> with its fallback content (not fetched by Edge)"
+                        .to_string(),
+                );
+                out.extend(sanitize_nodes(children, report));
+                let _ = attributes;
+            }
+            SsmlNode::Element { name, .. } if name == "lexicon" => {
+                report.push(
+                    "Removed <lexicon> reference (not honored by Edge; apply the lexicon locally)"
+                        .to_string(),
+                );
+            }
+            SsmlNode::SayAs {
+                interpret_as,
+                format,
+                children,
+            } => {
+                if SUPPORTED_SAY_AS.contains(&interpret_as.as_str()) {
+                    out.push(SsmlNode::SayAs {
+                        interpret_as,
+                        format,
+                        children: sanitize_nodes(children, report),
+                    });
+                } else {
+                    report.push(format!(
+                        "Unwrapped <say-as interpret-as=\"{}\"> (unsupported interpretation)",
+                        interpret_as
+                    ));
+                    out.extend(sanitize_nodes(children, report));
+                }
+            }
+            SsmlNode::Voice { name, children } => out.push(SsmlNode::Voice {
+                name,
+                children: sanitize_nodes(children, report),
+            }),
+            SsmlNode::Prosody {
+                rate,
+                pitch,
+                volume,
+                children,
+            } => out.push(SsmlNode::Prosody {
+                rate,
+                pitch,
+                volume,
+                children: sanitize_nodes(children, report),
+            }),
+            SsmlNode::Element {
+                name,
+                attributes,
+                children,
+            } => out.push(SsmlNode::Element {
+                name,
+                attributes,
+                children: sanitize_nodes(children, report),
+            }),
+            other => out.push(other),
         }
     }
+    out
+}
 
-    builder.build()
+/// Compile the inline mini-markup syntax into a complete SSML document, so
+/// CLI users get expressive control without writing XML:
+///
+/// - `[pause 500ms]` inserts a break (times or strengths like `strong`)
+/// - `*text*` and `**text**` add moderate and strong emphasis
+/// - `{rate:slow ...}`, `{pitch:high ...}`, `{volume:soft ...}` wrap nested
+///   content in a prosody element
+pub fn compile_markup(text: &str, voice: &str) -> Result<String, String> {
+    let mut chars = text.chars().peekable();
+    let body = compile_markup_fragment(&mut chars, None)?;
+    Ok(SSMLBuilder::new(voice).add_text(&body).build())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+fn compile_markup_fragment(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    stop: Option<char>,
+) -> Result<String, String> {
+    let mut out = String::new();
 
-    #[test]
-    fn test_ssml_builder_basic() {
+    while let Some(&c) = chars.peek() {
+        if Some(c) == stop {
+            chars.next();
+            return Ok(out);
+        }
+        chars.next();
+        match c {
+            '[' => {
+                let mut directive = String::new();
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    directive.push(c);
+                }
+                let directive = directive.trim();
+                match directive.strip_prefix("pause") {
+                    Some(value) => {
+                        let value = value.trim();
+                        if value.ends_with("ms") || value.ends_with('s') {
+                            out.push_str(&format!("<break time=\"{}\"/>", value));
+                        } else {
+                            out.push_str(&format!("<break strength=\"{}\"/>", value));
+                        }
+                    }
+                    None => return Err(format!("Unknown directive: [{}]", directive)),
+                }
+            }
+            '{' => {
+                let mut head = String::new();
+                loop {
+                    match chars.next() {
+                        Some(c) if c.is_whitespace() => break,
+                        Some('}') => {
+                            return Err(format!("Empty prosody block: {{{}}}", head));
+                        }
+                        Some(c) => head.push(c),
+                        None => return Err("Unclosed prosody block".to_string()),
+                    }
+                }
+                let (attr, value) = head
+                    .split_once(':')
+                    .ok_or_else(|| format!("Expected attr:value, found {{{}", head))?;
+                if !["rate", "pitch", "volume"].contains(&attr) {
+                    return Err(format!("Unknown prosody attribute: {}", attr));
+                }
+                let inner = compile_markup_fragment(chars, Some('}'))?;
+                out.push_str(&format!(
+                    "<prosody {}=\"{}\">{}</prosody>",
+                    attr,
+                    value,
+                    inner.trim_start()
+                ));
+            }
+            '*' => {
+                let strong = chars.peek() == Some(&'*');
+                if strong {
+                    chars.next();
+                }
+                let mut inner = String::new();
+                loop {
+                    match chars.next() {
+                        Some('*') => {
+                            if strong && chars.peek() == Some(&'*') {
+                                chars.next();
+                            }
+                            break;
+                        }
+                        Some(c) => inner.push(c),
+                        None => return Err("Unclosed emphasis marker".to_string()),
+                    }
+                }
+                let level = if strong { "strong" } else { "moderate" };
+                out.push_str(&format!(
+                    "<emphasis level=\"{}\">{}</emphasis>",
+                    level,
+                    escape_text(&inner)
+                ));
+            }
+            c => out.push_str(&escape_text(&c.to_string())),
+        }
+    }
+
+    match stop {
+        Some(c) => Err(format!("Unclosed block, expected {}", c)),
+        None => Ok(out),
+    }
+}
+
+/// Locale-aware text normalizer that expands abbreviations, decimal numbers,
+/// and units before synthesis, improving pronunciation consistency across
+/// voices. Built-in tables exist for English and German; additional
+/// expansions can be configured per language with
+/// [`TextNormalizer::with_abbreviation`].
+#[derive(Debug, Clone)]
+pub struct TextNormalizer {
+    locale: String,
+    abbreviations: Vec<(String, String)>,
+}
+
+const EN_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("Dr.", "Doctor"),
+    ("Mr.", "Mister"),
+    ("Mrs.", "Missus"),
+    ("St.", "Saint"),
+    ("etc.", "et cetera"),
+    ("e.g.", "for example"),
+    ("i.e.", "that is"),
+    ("vs.", "versus"),
+    ("approx.", "approximately"),
+    ("km/h", "kilometers per hour"),
+    ("mph", "miles per hour"),
+    ("m/s", "meters per second"),
+    ("kg", "kilograms"),
+    ("km", "kilometers"),
+    ("\u{00B0}C", "degrees Celsius"),
+    ("\u{00B0}F", "degrees Fahrenheit"),
+];
+
+const DE_ABBREVIATIONS: &[(&str, &str)] = &[
+    ("Dr.", "Doktor"),
+    ("z.B.", "zum Beispiel"),
+    ("usw.", "und so weiter"),
+    ("bzw.", "beziehungsweise"),
+    ("ca.", "circa"),
+    ("km/h", "Kilometer pro Stunde"),
+    ("m/s", "Meter pro Sekunde"),
+    ("kg", "Kilogramm"),
+    ("km", "Kilometer"),
+    ("\u{00B0}C", "Grad Celsius"),
+];
+
+impl TextNormalizer {
+    /// Create a normalizer for a locale (e.g., "en-US", "de-DE"); locales
+    /// without a built-in table fall back to the English expansions
+    pub fn new(locale: &str) -> Self {
+        let table = if locale.starts_with("de") {
+            DE_ABBREVIATIONS
+        } else {
+            EN_ABBREVIATIONS
+        };
+        Self {
+            locale: locale.to_string(),
+            abbreviations: table
+                .iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect(),
+        }
+    }
+
+    /// Add or override an abbreviation expansion
+    pub fn with_abbreviation(mut self, abbreviation: &str, expansion: &str) -> Self {
+        self.abbreviations
+            .retain(|(existing, _)| existing != abbreviation);
+        self.abbreviations
+            .insert(0, (abbreviation.to_string(), expansion.to_string()));
+        self
+    }
+
+    /// Expand abbreviations, units, and decimal numbers in the text
+    pub fn normalize(&self, text: &str) -> String {
+        let text = self.normalize_decimals(text);
+
+        text.split(' ')
+            .map(|token| {
+                let trailing = token
+                    .chars()
+                    .rev()
+                    .take_while(|c| matches!(c, ',' | ';' | ':' | '!' | '?' | ')'))
+                    .count();
+                let (core, punct) = token.split_at(token.len() - trailing);
+                match self.abbreviations.iter().find(|(abbr, _)| abbr == core) {
+                    Some((_, expansion)) => format!("{}{}", expansion, punct),
+                    None => token.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Make the decimal separator explicit ("3.14" reads as "3 point 14")
+    /// so decimals are not mistaken for dates or ordinals
+    fn normalize_decimals(&self, text: &str) -> String {
+        use regex::Regex;
+
+        let (pattern, word) = if self.locale.starts_with("de") {
+            (r"(\d+),(\d+)", "Komma")
+        } else {
+            (r"(\d+)\.(\d+)", "point")
+        };
+        Regex::new(pattern)
+            .unwrap()
+            .replace_all(text, format!("$1 {} $2", word).as_str())
+            .to_string()
+    }
+}
+
+/// How [`preprocess_emoji`] treats emoji found in input text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmojiMode {
+    /// Leave emoji untouched
+    Keep,
+    /// Remove emoji entirely
+    Strip,
+    /// Replace emoji with their spoken names ("😀" becomes "grinning face");
+    /// emoji without a known name are stripped
+    Verbalize,
+}
+
+/// Spoken names for commonly used emoji
+const EMOJI_NAMES: &[(char, &str)] = &[
+    ('\u{1F600}', "grinning face"),
+    ('\u{1F602}', "face with tears of joy"),
+    ('\u{1F60A}', "smiling face with smiling eyes"),
+    ('\u{1F60D}', "smiling face with heart eyes"),
+    ('\u{1F609}', "winking face"),
+    ('\u{1F62D}', "loudly crying face"),
+    ('\u{2764}', "red heart"),
+    ('\u{1F44D}', "thumbs up"),
+    ('\u{1F44F}', "clapping hands"),
+    ('\u{1F64F}', "folded hands"),
+    ('\u{1F389}', "party popper"),
+    ('\u{1F525}', "fire"),
+    ('\u{1F680}', "rocket"),
+    ('\u{2B50}', "star"),
+    ('\u{2600}', "sun"),
+    ('\u{2705}', "check mark"),
+    ('\u{26A0}', "warning sign"),
+    ('\u{1F4A1}', "light bulb"),
+    ('\u{1F3B5}', "musical note"),
+];
+
+/// Strip or verbalize emoji in input text before synthesis, since raw emoji
+/// produce awkward or skipped speech
+pub fn preprocess_emoji(text: &str, mode: EmojiMode) -> String {
+    if mode == EmojiMode::Keep {
+        return text.to_string();
+    }
+
+    let mut out = String::new();
+    for c in text.chars() {
+        if !is_emoji_char(c) {
+            out.push(c);
+            continue;
+        }
+        if mode == EmojiMode::Verbalize {
+            if let Some((_, name)) = EMOJI_NAMES.iter().find(|(emoji, _)| *emoji == c) {
+                if !out.ends_with(' ') && !out.is_empty() {
+                    out.push(' ');
+                }
+                out.push_str(name);
+                out.push(' ');
+            }
+        }
+    }
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Emoji blocks plus the joiners and modifiers that accompany them
+fn is_emoji_char(c: char) -> bool {
+    matches!(c as u32,
+        0x1F000..=0x1FAFF   // emoticons, symbols, transport, supplemental
+        | 0x2600..=0x27BF   // miscellaneous symbols and dingbats
+        | 0x2B00..=0x2BFF   // arrows and stars
+        | 0x2190..=0x21FF   // arrows
+        | 0xFE0E..=0xFE0F   // variation selectors
+        | 0x200D            // zero-width joiner
+        | 0x20E3            // combining enclosing keycap
+    )
+}
+
+/// SAPI phone tokens and their IPA equivalents for en-US, used to convert
+/// between the two alphabets the service accepts. Multi-character IPA
+/// symbols come first so reverse lookup can match greedily.
+const SAPI_IPA_PHONES: &[(&str, &str)] = &[
+    ("ch", "t\u{0283}"),
+    ("jh", "d\u{0292}"),
+    ("ey", "e\u{026A}"),
+    ("ay", "a\u{026A}"),
+    ("oy", "\u{0254}\u{026A}"),
+    ("aw", "a\u{028A}"),
+    ("ow", "o\u{028A}"),
+    ("th", "\u{03B8}"),
+    ("dh", "\u{00F0}"),
+    ("sh", "\u{0283}"),
+    ("zh", "\u{0292}"),
+    ("ng", "\u{014B}"),
+    ("iy", "i"),
+    ("ih", "\u{026A}"),
+    ("eh", "\u{025B}"),
+    ("ae", "\u{00E6}"),
+    ("aa", "\u{0251}"),
+    ("ah", "\u{028C}"),
+    ("ao", "\u{0254}"),
+    ("uh", "\u{028A}"),
+    ("uw", "u"),
+    ("er", "\u{025D}"),
+    ("ax", "\u{0259}"),
+    ("1", "\u{02C8}"),
+    ("2", "\u{02CC}"),
+    ("p", "p"),
+    ("b", "b"),
+    ("t", "t"),
+    ("d", "d"),
+    ("k", "k"),
+    ("g", "g"),
+    ("f", "f"),
+    ("v", "v"),
+    ("s", "s"),
+    ("z", "z"),
+    ("h", "h"),
+    ("m", "m"),
+    ("n", "n"),
+    ("l", "l"),
+    ("r", "r"),
+    ("w", "w"),
+    ("y", "j"),
+];
+
+/// Built-in IPA pronunciations for words the service commonly mangles
+const BUILTIN_WORD_PHONEMES: &[(&str, &str)] = &[
+    ("tomato", "t\u{0259}\u{02C8}me\u{026A}to\u{028A}"),
+    ("caramel", "\u{02C8}k\u{0251}rm\u{0259}l"),
+    ("nuclear", "\u{02C8}nukli\u{025A}"),
+    ("cache", "k\u{00E6}\u{0283}"),
+    ("niche", "ni\u{0283}"),
+    ("gif", "d\u{0292}\u{026A}f"),
+    ("sql", "\u{02C8}sikw\u{0259}l"),
+];
+
+/// Look up the built-in IPA pronunciation for a tricky word
+pub fn builtin_phoneme(word: &str) -> Option<&'static str> {
+    BUILTIN_WORD_PHONEMES
+        .iter()
+        .find(|(w, _)| w.eq_ignore_ascii_case(word))
+        .map(|(_, ph)| *ph)
+}
+
+/// Convert a whitespace-separated SAPI phoneme string to IPA, rejecting
+/// unknown phone tokens so bad transcriptions fail before reaching the
+/// service
+pub fn sapi_to_ipa(ph: &str) -> Result<String, String> {
+    let mut out = String::new();
+    for token in ph.split_whitespace() {
+        // Stress digits attach to the preceding vowel token (e.g., "ae 1")
+        let ipa = SAPI_IPA_PHONES
+            .iter()
+            .find(|(sapi, _)| *sapi == token.to_ascii_lowercase())
+            .map(|(_, ipa)| *ipa)
+            .ok_or_else(|| format!("Unknown SAPI phone: {}", token))?;
+        out.push_str(ipa);
+    }
+    Ok(out)
+}
+
+/// Convert an IPA phoneme string to whitespace-separated SAPI phones,
+/// rejecting IPA symbols outside the SAPI en-US phone set
+pub fn ipa_to_sapi(ph: &str) -> Result<String, String> {
+    let mut tokens = Vec::new();
+    let mut rest = ph.trim();
+    while !rest.is_empty() {
+        // Greedy: multi-character IPA symbols appear first in the table
+        let matched = SAPI_IPA_PHONES
+            .iter()
+            .find(|(_, ipa)| rest.starts_with(ipa))
+            .ok_or_else(|| {
+                format!(
+                    "IPA symbol not representable in SAPI: {}",
+                    rest.chars().next().unwrap()
+                )
+            })?;
+        tokens.push(matched.0);
+        rest = &rest[matched.1.len()..];
+    }
+    Ok(tokens.join(" "))
+}
+
+/// A PLS (Pronunciation Lexicon Specification) lexicon, loaded from XML and
+/// applied locally for backends that do not fetch `<lexicon uri=…>`
+/// references themselves.
+#[derive(Debug, Clone)]
+pub struct Lexicon {
+    /// Phonetic alphabet the phoneme entries use (e.g., "ipa")
+    pub alphabet: String,
+    entries: Vec<LexiconEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct LexiconEntry {
+    grapheme: String,
+    phoneme: Option<String>,
+    alias: Option<String>,
+}
+
+impl Lexicon {
+    /// Parse a PLS lexicon document
+    pub fn from_pls(xml: &str) -> Result<Self, String> {
+        use quick_xml::events::Event;
+        use quick_xml::Reader;
+
+        let mut reader = Reader::from_str(xml);
+        let mut alphabet = "ipa".to_string();
+        let mut entries = Vec::new();
+        let mut current: Option<LexiconEntry> = None;
+        let mut text_target: Option<&str> = None;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(element)) => {
+                    match element.name().as_ref() {
+                        b"lexicon" => {
+                            if let Some(value) = element.attributes().flatten().find_map(|a| {
+                                (a.key.as_ref() == b"alphabet")
+                                    .then(|| String::from_utf8_lossy(&a.value).to_string())
+                            }) {
+                                alphabet = value;
+                            }
+                        }
+                        b"lexeme" => {
+                            current = Some(LexiconEntry {
+                                grapheme: String::new(),
+                                phoneme: None,
+                                alias: None,
+                            });
+                        }
+                        b"grapheme" => text_target = Some("grapheme"),
+                        b"phoneme" => text_target = Some("phoneme"),
+                        b"alias" => text_target = Some("alias"),
+                        _ => {}
+                    }
+                }
+                Ok(Event::Text(text)) => {
+                    if let (Some(entry), Some(target)) = (&mut current, text_target) {
+                        let value = text
+                            .unescape()
+                            .map_err(|e| format!("XML error: {}", e))?
+                            .trim()
+                            .to_string();
+                        match target {
+                            "grapheme" => entry.grapheme = value,
+                            "phoneme" => entry.phoneme = Some(value),
+                            _ => entry.alias = Some(value),
+                        }
+                    }
+                }
+                Ok(Event::End(element)) => {
+                    match element.name().as_ref() {
+                        b"lexeme" => {
+                            if let Some(entry) = current.take() {
+                                if !entry.grapheme.is_empty() {
+                                    entries.push(entry);
+                                }
+                            }
+                        }
+                        b"grapheme" | b"phoneme" | b"alias" => text_target = None,
+                        _ => {}
+                    }
+                }
+                Ok(Event::Eof) => break,
+                Ok(_) => {}
+                Err(e) => return Err(format!("XML error: {}", e)),
+            }
+        }
+
+        Ok(Self { alphabet, entries })
+    }
+
+    /// Rewrite matching words in plain text into `<phoneme>`/`<sub>` elements
+    /// so the lexicon takes effect without service-side support. Matching is
+    /// case-insensitive on whole words; other text is XML-escaped.
+    pub fn apply(&self, text: &str) -> String {
+        let mut out = String::new();
+        let mut word = String::new();
+
+        let flush = |word: &mut String, out: &mut String| {
+            if word.is_empty() {
+                return;
+            }
+            let entry = self
+                .entries
+                .iter()
+                .find(|e| e.grapheme.eq_ignore_ascii_case(word));
+            match entry {
+                Some(LexiconEntry {
+                    phoneme: Some(ph), ..
+                }) => out.push_str(&format!(
+                    "<phoneme alphabet=\"{}\" ph=\"{}\">{}</phoneme>",
+                    self.alphabet,
+                    escape_text(ph),
+                    escape_text(word)
+                )),
+                Some(LexiconEntry {
+                    alias: Some(alias), ..
+                }) => out.push_str(&format!(
+                    "<sub alias=\"{}\">{}</sub>",
+                    escape_text(alias),
+                    escape_text(word)
+                )),
+                _ => out.push_str(&escape_text(word)),
+            }
+            word.clear();
+        };
+
+        for c in text.chars() {
+            if c.is_alphanumeric() || c == '\'' || c == '-' {
+                word.push(c);
+            } else {
+                flush(&mut word, &mut out);
+                out.push_str(&escape_text(&c.to_string()));
+            }
+        }
+        flush(&mut word, &mut out);
+        out
+    }
+}
+
+/// Split a long SSML document at safe element boundaries into multiple valid
+/// documents, each at most `max_len` characters when serialized. Splits only
+/// happen between sibling nodes — never inside a prosody, voice, or other
+/// element — so the long-text pipeline also works for SSML input. A single
+/// unsplittable node larger than `max_len` becomes its own document.
+pub fn split_ssml(ssml: &str, max_len: usize) -> Result<Vec<String>, String> {
+    let document = parse(ssml)?;
+
+    // Split inside a single root <voice> wrapper when present, so each chunk
+    // keeps the voice; otherwise split the root's own children.
+    let (voice, units) = match document.children.as_slice() {
+        [SsmlNode::Voice { name, children }] => (Some(name.clone()), children.clone()),
+        _ => (None, document.children.clone()),
+    };
+
+    let wrap = |nodes: Vec<SsmlNode>| {
+        let children = match &voice {
+            Some(name) => vec![SsmlNode::Voice {
+                name: name.clone(),
+                children: nodes,
+            }],
+            None => nodes,
+        };
+        SsmlDocument {
+            lang: document.lang.clone(),
+            children,
+        }
+        .to_ssml()
+    };
+
+    let mut documents = Vec::new();
+    let mut current: Vec<SsmlNode> = Vec::new();
+    for unit in units {
+        let mut candidate = current.clone();
+        candidate.push(unit.clone());
+        if !current.is_empty() && wrap(candidate).len() > max_len {
+            documents.push(wrap(std::mem::take(&mut current)));
+        }
+        current.push(unit);
+    }
+    if !current.is_empty() {
+        documents.push(wrap(current));
+    }
+
+    Ok(documents)
+}
+
+/// Split long plain text into chunks of at most `max_len` characters at
+/// paragraph and sentence boundaries, so each chunk can be synthesized
+/// separately and the audio concatenated. A single sentence longer than
+/// `max_len` is hard-split at whitespace as a last resort.
+pub fn split_text(text: &str, max_len: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    let push_unit = |unit: &str, chunks: &mut Vec<String>, current: &mut String| {
+        if !current.is_empty() && current.len() + unit.len() + 1 > max_len {
+            chunks.push(std::mem::take(current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(unit);
+    };
+
+    for paragraph in text.split("\n\n") {
+        let paragraph = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+        if paragraph.is_empty() {
+            continue;
+        }
+        for sentence in split_sentences(&paragraph) {
+            if sentence.len() > max_len {
+                // Last resort: break an oversized sentence between words
+                for word in sentence.split_whitespace() {
+                    push_unit(word, &mut chunks, &mut current);
+                }
+            } else {
+                push_unit(&sentence, &mut chunks, &mut current);
+            }
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Baseline speaking speed used for duration estimates, in words per minute
+const ESTIMATE_WORDS_PER_MINUTE: f64 = 150.0;
+
+/// Estimate word boundaries for plain text at the baseline speaking rate,
+/// weighting each word's share of time by its length. Used for captions
+/// when the synthesis path does not report real boundary events.
+pub fn estimate_word_boundaries(text: &str) -> Vec<crate::tts_client::WordBoundary> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let seconds_per_word = 60.0 / ESTIMATE_WORDS_PER_MINUTE;
+    let average_len: f64 = words.iter().map(|w| w.len() as f64).sum::<f64>()
+        / words.len().max(1) as f64;
+
+    let mut boundaries = Vec::with_capacity(words.len());
+    let mut offset = 0.0;
+    for word in words {
+        let duration = seconds_per_word * (word.len() as f64 / average_len.max(1.0));
+        boundaries.push(crate::tts_client::WordBoundary::new(
+            word.to_string(),
+            std::time::Duration::from_secs_f64(offset),
+            std::time::Duration::from_secs_f64(duration),
+        ));
+        offset += duration;
+    }
+    boundaries
+}
+
+/// Estimate the spoken duration of an SSML document, accounting for break
+/// elements and prosody rate, so schedulers can plan before synthesizing.
+/// Returns an error when the document does not parse.
+pub fn estimate_duration(ssml: &str) -> Result<std::time::Duration, String> {
+    let document = parse(ssml)?;
+    let mut seconds = 0.0;
+    for child in &document.children {
+        seconds += estimate_node_seconds(child, 1.0);
+    }
+    Ok(std::time::Duration::from_secs_f64(seconds))
+}
+
+fn estimate_node_seconds(node: &SsmlNode, rate: f64) -> f64 {
+    match node {
+        SsmlNode::Text(text) => {
+            let words = text.split_whitespace().count() as f64;
+            words * 60.0 / (ESTIMATE_WORDS_PER_MINUTE * rate)
+        }
+        SsmlNode::Break { time, strength } => {
+            if let Some(time) = time {
+                parse_time_value(time).unwrap_or(0.0)
+            } else {
+                match strength.as_deref() {
+                    Some("none") => 0.0,
+                    Some("x-weak") => 0.1,
+                    Some("weak") => 0.25,
+                    Some("strong") => 0.75,
+                    Some("x-strong") => 1.0,
+                    _ => 0.5,
+                }
+            }
+        }
+        SsmlNode::Prosody {
+            rate: node_rate,
+            children,
+            ..
+        } => {
+            let rate = rate * node_rate.as_deref().map(rate_multiplier).unwrap_or(1.0);
+            children
+                .iter()
+                .map(|c| estimate_node_seconds(c, rate))
+                .sum()
+        }
+        SsmlNode::Voice { children, .. }
+        | SsmlNode::SayAs { children, .. }
+        | SsmlNode::Element { children, .. } => children
+            .iter()
+            .map(|c| estimate_node_seconds(c, rate))
+            .sum(),
+    }
+}
+
+/// Parse a "200ms" or "1.5s" time value into seconds
+fn parse_time_value(value: &str) -> Option<f64> {
+    if let Some(ms) = value.strip_suffix("ms") {
+        ms.trim().parse::<f64>().ok().map(|v| v / 1000.0)
+    } else if let Some(s) = value.strip_suffix('s') {
+        s.trim().parse::<f64>().ok()
+    } else {
+        None
+    }
+}
+
+/// Map a prosody rate value to a speed multiplier
+fn rate_multiplier(rate: &str) -> f64 {
+    match rate {
+        "x-slow" => 0.5,
+        "slow" => 0.75,
+        "medium" => 1.0,
+        "fast" => 1.5,
+        "x-fast" => 2.0,
+        other => {
+            // Relative percentages: "+20%" speeds up, "-20%" slows down
+            if let Some(percent) = other.strip_suffix('%') {
+                percent
+                    .trim_start_matches('+')
+                    .parse::<f64>()
+                    .map(|p| (1.0 + p / 100.0).max(0.1))
+                    .unwrap_or(1.0)
+            } else {
+                1.0
+            }
+        }
+    }
+}
+
+/// Remove all markup from an SSML document and return the speakable text,
+/// for character counting, logging, and caption fallback. Whitespace is
+/// collapsed to single spaces between text runs.
+pub fn strip_ssml(ssml: &str) -> String {
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+
+    let mut reader = Reader::from_str(ssml);
+    let mut parts: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Text(text)) => {
+                let text = text
+                    .unescape()
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|_| String::from_utf8_lossy(&text).to_string());
+                let trimmed = text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if !trimmed.is_empty() {
+                    parts.push(trimmed);
+                }
+            }
+            Ok(Event::Eof) | Err(_) => break,
+            Ok(_) => {}
+        }
+    }
+
+    parts.join(" ")
+}
+
+/// Turn plain prose into SSML with sensible pauses so unstructured text
+/// sounds less robotic: sentences get short breaks, paragraphs longer ones,
+/// and headings (short lines without ending punctuation) and ellipses are
+/// followed by extended pauses. Optional prosody settings wrap the whole
+/// document, typically sourced from config defaults.
+pub fn text_to_ssml(
+    text: &str,
+    voice: &str,
+    rate: Option<&str>,
+    pitch: Option<&str>,
+    volume: Option<&str>,
+) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            parts.push("<break time=\"600ms\"/>".to_string());
+            continue;
+        }
+
+        let escaped = escape_text(trimmed);
+        if is_heading_line(trimmed) {
+            parts.push(escaped);
+            parts.push("<break time=\"800ms\"/>".to_string());
+            continue;
+        }
+
+        for sentence in split_sentences(&escaped) {
+            let pause = if sentence.ends_with("...") || sentence.ends_with('\u{2026}') {
+                "<break time=\"500ms\"/>"
+            } else {
+                "<break time=\"300ms\"/>"
+            };
+            parts.push(sentence.trim().to_string());
+            parts.push(pause.to_string());
+        }
+    }
+
+    // Trailing pause adds nothing to the audio
+    if parts.last().map(|p| p.starts_with("<break")).unwrap_or(false) {
+        parts.pop();
+    }
+
+    let content = parts.join("");
+    let builder = SSMLBuilder::new(voice);
+    if rate.is_some() || pitch.is_some() || volume.is_some() {
+        builder.add_prosody(&content, rate, pitch, volume).build()
+    } else {
+        builder.add_text(&content).build()
+    }
+}
+
+/// A short line with no terminal punctuation reads as a heading
+fn is_heading_line(line: &str) -> bool {
+    line.len() < 60 && !line.ends_with(['.', '!', '?', ':', ',', ';'])
+}
+
+/// Split on sentence-ending punctuation, keeping the punctuation with the
+/// sentence and treating ellipses as a single terminator
+fn split_sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+        let at_end = matches!(c, '.' | '!' | '?' | '\u{2026}')
+            && chars.peek().map(|n| n.is_whitespace()).unwrap_or(true)
+            && chars.peek() != Some(&'.');
+        if at_end && !current.trim().is_empty() {
+            sentences.push(current.trim().to_string());
+            current.clear();
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+    sentences
+}
+
+/// Escape XML special characters in text content
+fn escape_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Convert Markdown to SSML so documentation and notes can be narrated
+/// directly: headings become pauses plus strong emphasis, bold/italic become
+/// emphasis, list items are separated by short breaks, and block quotes are
+/// read more softly.
+pub fn markdown_to_ssml(markdown: &str, voice: &str) -> String {
+    let mut builder = SSMLBuilder::new(voice);
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            builder = builder.add_break("400ms");
+        } else if let Some(heading) = trimmed.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            builder = builder
+                .add_break("500ms")
+                .add_emphasis(&markdown_inline(heading), "strong")
+                .add_break("500ms");
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            builder = builder.add_prosody(
+                &markdown_inline(quote.trim()),
+                None,
+                None,
+                Some("soft"),
+            );
+        } else if let Some(item) = markdown_list_item(trimmed) {
+            builder = builder
+                .add_text(&markdown_inline(item))
+                .add_break("300ms");
+        } else {
+            builder = builder.add_text(&markdown_inline(trimmed)).add_text(" ");
+        }
+    }
+
+    builder.build()
+}
+
+/// Strip a leading bullet or ordered-list marker, if any
+fn markdown_list_item(line: &str) -> Option<&str> {
+    if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(item);
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some(item);
+        }
+    }
+    None
+}
+
+/// Convert inline Markdown spans to SSML and escape XML special characters
+fn markdown_inline(text: &str) -> String {
+    use regex::Regex;
+
+    let escaped = escape_text(text);
+
+    let bold = Regex::new(r"\*\*([^*]+)\*\*|__([^_]+)__").unwrap();
+    let converted = bold.replace_all(&escaped, |caps: &regex::Captures<'_>| {
+        let inner = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+        format!("<emphasis level=\"strong\">{}</emphasis>", inner)
+    });
+
+    let italic = Regex::new(r"\*([^*]+)\*|_([^_]+)_").unwrap();
+    italic
+        .replace_all(&converted, |caps: &regex::Captures<'_>| {
+            let inner = caps.get(1).or_else(|| caps.get(2)).unwrap().as_str();
+            format!("<emphasis level=\"moderate\">{}</emphasis>", inner)
+        })
+        .to_string()
+}
+
+/// Create SSML with breaks between text parts
+pub fn create_break_ssml(text_parts: &[&str], voice: &str, break_time: &str) -> String {
+    let mut builder = SSMLBuilder::new(voice);
+
+    for (i, part) in text_parts.iter().enumerate() {
+        builder = builder.add_text(part);
+        if i < text_parts.len() - 1 {
+            builder = builder.add_break(break_time);
+        }
+    }
+
+    builder.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ssml_builder_basic() {
         let ssml = SSMLBuilder::new("en-US-AriaNeural")
             .add_text("Hello, world!")
             .build();
@@ -409,6 +2346,598 @@ mod tests {
         assert!(ssml.contains("<break time=\"2s\"/>"));
     }
 
+    const SAMPLE_PLS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<lexicon version="1.0" alphabet="ipa" xml:lang="en-US">
+  <lexeme>
+    <grapheme>tomato</grapheme>
+    <phoneme>t&#x259;&#x2C8;m&#x251;to&#x28A;</phoneme>
+  </lexeme>
+  <lexeme>
+    <grapheme>WWW</grapheme>
+    <alias>World Wide Web</alias>
+  </lexeme>
+</lexicon>"#;
+
+    #[test]
+    fn test_nested_scopes() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .prosody(Some("slow"), None, None, |b| {
+                b.text("so ")
+                    .emphasis("strong", |b| b.text("very"))
+                    .text(" calm")
+            })
+            .lang("fr-FR", |b| b.text("très bien"))
+            .build();
+
+        assert!(ssml.contains(
+            "<prosody rate=\"slow\">so <emphasis level=\"strong\">very</emphasis> calm</prosody>"
+        ));
+        assert!(ssml.contains("<lang xml:lang=\"fr-FR\">très bien</lang>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_nested_scope_escapes_text() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .emphasis("moderate", |b| b.text("a < b"))
+            .build();
+
+        assert!(ssml.contains("<emphasis level=\"moderate\">a &lt; b</emphasis>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_ssml_rewrites_unsupported_elements() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_lexicon("https://example.com/lex.xml")
+            .add_audio("https://example.com/chime.mp3", "ding")
+            .add_say_as("42", "cardinal", None)
+            .add_say_as("NASA", "acronym", None)
+            .build();
+
+        let (sanitized, report) = sanitize_ssml(&ssml).unwrap();
+        assert!(!sanitized.contains("<lexicon"));
+        assert!(!sanitized.contains("<audio"));
+        assert!(sanitized.contains("ding"));
+        assert!(sanitized.contains("<say-as interpret-as=\"cardinal\">42</say-as>"));
+        assert!(!sanitized.contains("acronym"));
+        assert!(sanitized.contains("NASA"));
+        assert_eq!(report.len(), 3);
+        assert!(SSMLValidator::validate(&sanitized).is_empty());
+    }
+
+    #[test]
+    fn test_sanitize_ssml_clean_document_unchanged() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("Hello", Some("slow"), None, None)
+            .build();
+
+        let (sanitized, report) = sanitize_ssml(&ssml).unwrap();
+        assert!(report.is_empty());
+        assert!(sanitized.contains("<prosody rate=\"slow\">Hello</prosody>"));
+    }
+
+    #[test]
+    fn test_compile_markup_basics() {
+        let ssml = compile_markup(
+            "Hello [pause 500ms] *world* and {rate:slow take it easy}",
+            "en-US-AriaNeural",
+        )
+        .unwrap();
+
+        assert!(ssml.contains("<break time=\"500ms\"/>"));
+        assert!(ssml.contains("<emphasis level=\"moderate\">world</emphasis>"));
+        assert!(ssml.contains("<prosody rate=\"slow\">take it easy</prosody>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_compile_markup_nested_and_strong() {
+        let ssml = compile_markup(
+            "{rate:slow outer **inner** [pause weak] done}",
+            "en-US-AriaNeural",
+        )
+        .unwrap();
+
+        assert!(ssml.contains("<prosody rate=\"slow\">outer <emphasis level=\"strong\">inner</emphasis> <break strength=\"weak\"/> done</prosody>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_compile_markup_errors() {
+        assert!(compile_markup("{rate:slow unclosed", "v").is_err());
+        assert!(compile_markup("*unclosed", "v").is_err());
+        assert!(compile_markup("{speed:slow x}", "v").is_err());
+        assert!(compile_markup("[sleep 5s]", "v").is_err());
+    }
+
+    #[test]
+    fn test_ssml_builder_break_strength() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("First")
+            .add_break_strength(BreakStrength::Strong)
+            .add_text("second")
+            .add_break_with(Some("750ms"), Some(BreakStrength::Medium))
+            .add_text("third")
+            .build();
+
+        assert!(ssml.contains("<break strength=\"strong\"/>"));
+        assert!(ssml.contains("<break time=\"750ms\" strength=\"medium\"/>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_paragraph_and_sentence() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_paragraph(&["First sentence.", "Second sentence."])
+            .add_sentence("A standalone sentence.")
+            .build();
+
+        assert!(ssml.contains("<p><s>First sentence.</s><s>Second sentence.</s></p>"));
+        assert!(ssml.contains("<s>A standalone sentence.</s>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_normalizer_expands_abbreviations_and_units() {
+        let normalizer = TextNormalizer::new("en-US");
+        assert_eq!(
+            normalizer.normalize("Dr. Smith drove at 80 km/h, etc."),
+            "Doctor Smith drove at 80 kilometers per hour, et cetera"
+        );
+    }
+
+    #[test]
+    fn test_normalizer_decimal_numbers() {
+        let en = TextNormalizer::new("en-US");
+        assert_eq!(en.normalize("pi is 3.14"), "pi is 3 point 14");
+
+        let de = TextNormalizer::new("de-DE");
+        assert_eq!(de.normalize("Pi ist 3,14"), "Pi ist 3 Komma 14");
+    }
+
+    #[test]
+    fn test_normalizer_locale_tables() {
+        let de = TextNormalizer::new("de-DE");
+        assert_eq!(
+            de.normalize("Dr. Meier fuhr z.B. 50 km/h"),
+            "Doktor Meier fuhr zum Beispiel 50 Kilometer pro Stunde"
+        );
+    }
+
+    #[test]
+    fn test_normalizer_custom_abbreviation() {
+        let normalizer = TextNormalizer::new("en-US").with_abbreviation("TTS", "text to speech");
+        assert_eq!(normalizer.normalize("TTS demo"), "text to speech demo");
+    }
+
+    #[test]
+    fn test_emoji_strip() {
+        assert_eq!(
+            preprocess_emoji("Great job \u{1F600}\u{1F389}!", EmojiMode::Strip),
+            "Great job !"
+        );
+    }
+
+    #[test]
+    fn test_emoji_verbalize() {
+        assert_eq!(
+            preprocess_emoji("Deployed \u{1F680} to prod", EmojiMode::Verbalize),
+            "Deployed rocket to prod"
+        );
+        // Emoji with no known name are stripped instead of left raw
+        assert_eq!(
+            preprocess_emoji("odd \u{1F9EA} one", EmojiMode::Verbalize),
+            "odd one"
+        );
+    }
+
+    #[test]
+    fn test_emoji_keep() {
+        let text = "hi \u{1F600}";
+        assert_eq!(preprocess_emoji(text, EmojiMode::Keep), text);
+    }
+
+    #[test]
+    fn test_ssml_validation_prosody_ranges() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("Hi", Some("+350%"), Some("+50st"), Some("-200dB"))
+            .build();
+
+        let errors = SSMLValidator::validate(&ssml);
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].contains("rate out of range"));
+        assert!(errors[1].contains("pitch out of range"));
+        assert!(errors[2].contains("volume out of range"));
+    }
+
+    #[test]
+    fn test_ssml_validation_prosody_ranges_accept_valid_values() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("Hi", Some("-50%"), Some("200Hz"), Some("+5dB"))
+            .build();
+
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_sapi_to_ipa_conversion() {
+        assert_eq!(sapi_to_ipa("t ax 1 m ey t ow").unwrap(), "tə\u{02C8}me\u{026A}to\u{028A}");
+        assert!(sapi_to_ipa("t qx").is_err());
+    }
+
+    #[test]
+    fn test_ipa_to_sapi_round_trip() {
+        let ipa = sapi_to_ipa("k ae sh").unwrap();
+        assert_eq!(ipa_to_sapi(&ipa).unwrap(), "k ae sh");
+        assert!(ipa_to_sapi("k\u{0281}").is_err());
+    }
+
+    #[test]
+    fn test_tricky_word_phonemes() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_tricky_word("GIF")
+            .add_tricky_word("unremarkable")
+            .build();
+
+        assert!(ssml.contains("<phoneme alphabet=\"ipa\""));
+        assert!(ssml.contains(">GIF</phoneme>"));
+        assert!(ssml.contains("unremarkable"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_validation_phoneme_alphabet() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_phoneme("cat", "klingon", "kat")
+            .build();
+
+        let errors = SSMLValidator::validate(&ssml);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Invalid phoneme alphabet"));
+    }
+
+    #[test]
+    fn test_ssml_builder_lexicon_reference() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("tomato")
+            .add_lexicon("https://example.com/lexicon.xml")
+            .build();
+
+        assert!(ssml.contains("<lexicon uri=\"https://example.com/lexicon.xml\"/>"));
+        // The reference precedes the content it applies to
+        assert!(ssml.find("<lexicon").unwrap() < ssml.find("tomato").unwrap());
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_lexicon_local_application() {
+        let lexicon = Lexicon::from_pls(SAMPLE_PLS).unwrap();
+        let rewritten = lexicon.apply("Put the tomato on the WWW & enjoy.");
+
+        assert!(rewritten.contains("<phoneme alphabet=\"ipa\""));
+        assert!(rewritten.contains(">tomato</phoneme>"));
+        assert!(rewritten.contains("<sub alias=\"World Wide Web\">WWW</sub>"));
+        assert!(rewritten.contains("&amp;"));
+    }
+
+    #[test]
+    fn test_lexicon_matching_is_case_insensitive() {
+        let lexicon = Lexicon::from_pls(SAMPLE_PLS).unwrap();
+        assert!(lexicon.apply("Tomato soup").contains("</phoneme>"));
+    }
+
+    #[test]
+    fn test_split_ssml_at_element_boundaries() {
+        let mut builder = SSMLBuilder::new("en-US-AriaNeural");
+        for i in 0..6 {
+            builder = builder.add_prosody(
+                &format!("Sentence number {} of the long document.", i),
+                Some("slow"),
+                None,
+                None,
+            );
+        }
+        let ssml = builder.build();
+
+        let chunks = split_ssml(&ssml, 400).unwrap();
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(SSMLValidator::validate(chunk).is_empty());
+            // Prosody elements were never split apart
+            assert_eq!(chunk.matches("<prosody").count(), chunk.matches("</prosody>").count());
+        }
+        let total: usize = chunks
+            .iter()
+            .map(|c| c.matches("<prosody").count())
+            .sum();
+        assert_eq!(total, 6);
+    }
+
+    #[test]
+    fn test_split_ssml_fits_returns_single_document() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural").add_text("Hi").build();
+        let chunks = split_ssml(&ssml, 10_000).unwrap();
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_split_text_at_sentence_boundaries() {
+        let text = "First sentence here. Second sentence here. Third sentence here.";
+        let chunks = split_text(text, 45);
+        assert_eq!(
+            chunks,
+            vec![
+                "First sentence here. Second sentence here.",
+                "Third sentence here."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_text_hard_splits_oversized_sentence() {
+        let text = "one two three four five six seven eight nine ten";
+        let chunks = split_text(text, 20);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.len() <= 20));
+        assert_eq!(chunks.join(" "), text);
+    }
+
+    #[test]
+    fn test_estimate_duration_counts_words_and_breaks() {
+        // 5 words at 150 wpm = 2s, plus a 3s break
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("one two three four five")
+            .add_break("3s")
+            .build();
+
+        let estimate = estimate_duration(&ssml).unwrap();
+        assert_eq!(estimate, std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_estimate_duration_respects_prosody_rate() {
+        let slow = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("one two three four five", Some("x-slow"), None, None)
+            .build();
+        let fast = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("one two three four five", Some("+100%"), None, None)
+            .build();
+
+        assert_eq!(
+            estimate_duration(&slow).unwrap(),
+            std::time::Duration::from_secs(4)
+        );
+        assert_eq!(
+            estimate_duration(&fast).unwrap(),
+            std::time::Duration::from_secs(1)
+        );
+    }
+
+    #[test]
+    fn test_strip_ssml_returns_plain_text() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("Hello")
+            .add_break("1s")
+            .add_emphasis("world", "strong")
+            .build();
+
+        assert_eq!(strip_ssml(&ssml), "Hello world");
+    }
+
+    #[test]
+    fn test_strip_ssml_unescapes_entities() {
+        let ssml = r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">Tom &amp; Jerry</speak>"#;
+        assert_eq!(strip_ssml(ssml), "Tom & Jerry");
+    }
+
+    #[test]
+    fn test_parse_typed_nodes() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_prosody("Hello", Some("slow"), None, None)
+            .add_break("2s")
+            .add_say_as("42", "cardinal", None)
+            .build();
+
+        let document = parse(&ssml).unwrap();
+        assert_eq!(document.lang, "en-US");
+        assert_eq!(document.children.len(), 1);
+
+        match &document.children[0] {
+            SsmlNode::Voice { name, children } => {
+                assert_eq!(name, "en-US-AriaNeural");
+                assert!(matches!(
+                    &children[0],
+                    SsmlNode::Prosody { rate: Some(r), .. } if r == "slow"
+                ));
+                assert!(matches!(
+                    &children[1],
+                    SsmlNode::Break { time: Some(t), .. } if t == "2s"
+                ));
+                assert!(matches!(
+                    &children[2],
+                    SsmlNode::SayAs { interpret_as, .. } if interpret_as == "cardinal"
+                ));
+            }
+            other => panic!("expected voice node, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_express_as("Hi", "cheerful")
+            .add_prosody("there", None, Some("high"), None)
+            .build();
+
+        let document = parse(&ssml).unwrap();
+        let reparsed = parse(&document.to_ssml()).unwrap();
+        assert_eq!(document, reparsed);
+        assert!(SSMLValidator::validate(&document.to_ssml()).is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_non_speak_root() {
+        assert!(parse("<voice name=\"x\">hi</voice>").is_err());
+    }
+
+    #[test]
+    fn test_text_to_ssml_sentence_breaks() {
+        let ssml = text_to_ssml("First sentence. Second sentence!", "en-US-AriaNeural", None, None, None);
+
+        assert!(ssml.contains("First sentence.<break time=\"300ms\"/>"));
+        assert!(ssml.contains("Second sentence!"));
+        assert!(!ssml.ends_with("<break"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_text_to_ssml_headings_and_ellipses() {
+        let text = "Chapter One\nIt was a dark night... and stormy too.";
+        let ssml = text_to_ssml(text, "en-US-AriaNeural", None, None, None);
+
+        assert!(ssml.contains("Chapter One<break time=\"800ms\"/>"));
+        assert!(ssml.contains("night...<break time=\"500ms\"/>"));
+    }
+
+    #[test]
+    fn test_text_to_ssml_prosody_defaults() {
+        let ssml = text_to_ssml("Hello there.", "en-US-AriaNeural", Some("-10%"), None, None);
+        assert!(ssml.contains("<prosody rate=\"-10%\">"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_markdown_to_ssml_structure() {
+        let markdown = "# Release Notes\n\nThe **parser** is now *faster*.\n\n- easier setup\n- fewer bugs\n\n> quoted remark";
+        let ssml = markdown_to_ssml(markdown, "en-US-AriaNeural");
+
+        assert!(ssml.contains("<emphasis level=\"strong\">Release Notes</emphasis>"));
+        assert!(ssml.contains("<emphasis level=\"strong\">parser</emphasis>"));
+        assert!(ssml.contains("<emphasis level=\"moderate\">faster</emphasis>"));
+        assert!(ssml.contains("easier setup<break time=\"300ms\"/>"));
+        assert!(ssml.contains("<prosody volume=\"soft\">quoted remark</prosody>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_markdown_to_ssml_escapes_xml() {
+        let ssml = markdown_to_ssml("use a < b & c", "en-US-AriaNeural");
+        assert!(ssml.contains("a &lt; b &amp; c"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_say_as_helpers() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_date("2024-01-15", "ymd")
+            .add_time("2:30pm", "hms12")
+            .add_currency("$42.50")
+            .add_telephone("(888) 555-1212")
+            .add_spell_out("TTS")
+            .add_ordinal("3")
+            .add_fraction("3/8")
+            .build();
+
+        assert!(ssml.contains("<say-as interpret-as=\"date\" format=\"ymd\">2024-01-15</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"time\" format=\"hms12\">2:30pm</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"currency\">$42.50</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"telephone\">(888) 555-1212</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"characters\">TTS</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"ordinal\">3</say-as>"));
+        assert!(ssml.contains("<say-as interpret-as=\"fraction\">3/8</say-as>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_lang() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("She said ")
+            .add_lang("fr-FR", "bonjour tout le monde")
+            .add_text(" and waved.")
+            .build();
+
+        assert!(ssml.contains("<lang xml:lang=\"fr-FR\">bonjour tout le monde</lang>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_silence() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_silence("Sentenceboundary", "200ms")
+            .add_text("Hello. World.")
+            .build();
+
+        assert!(ssml.contains("<mstts:silence type=\"Sentenceboundary\" value=\"200ms\"/>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_validation_silence_attributes() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_silence("Everywhere", "fast")
+            .build();
+
+        let errors = SSMLValidator::validate(&ssml);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("Invalid mstts:silence type"));
+        assert!(errors[1].contains("Invalid mstts:silence value"));
+    }
+
+    #[test]
+    fn test_ssml_builder_bookmark() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_text("Flowers")
+            .add_bookmark("flower_photo")
+            .add_text("and trees")
+            .build();
+
+        assert!(ssml.contains("<bookmark mark=\"flower_photo\"/>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_audio() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_audio("https://example.com/chime.mp3", "ding")
+            .build();
+
+        assert!(ssml.contains("<audio src=\"https://example.com/chime.mp3\">ding</audio>"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_builder_audio_without_fallback() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_audio("https://example.com/chime.mp3", "")
+            .build();
+
+        assert!(ssml.contains("<audio src=\"https://example.com/chime.mp3\"/>"));
+    }
+
+    #[test]
+    fn test_ssml_builder_express_as() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_express_as_with("Once upon a time", "narration", Some(1.5), Some("OlderAdultFemale"))
+            .build();
+
+        assert!(ssml.contains("<mstts:express-as style=\"narration\" styledegree=\"1.5\" role=\"OlderAdultFemale\">"));
+        assert!(ssml.contains("xmlns:mstts"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_ssml_validation_express_as_attributes() {
+        let ssml = SSMLBuilder::new("en-US-AriaNeural")
+            .add_express_as_with("Hi", "cheerful", Some(5.0), Some("Robot"))
+            .build();
+
+        let errors = SSMLValidator::validate(&ssml);
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("styledegree"));
+        assert!(errors[1].contains("role"));
+    }
+
     #[test]
     fn test_ssml_validation_valid() {
         let ssml = SSMLBuilder::new("en-US-AriaNeural")
@@ -426,6 +2955,31 @@ mod tests {
         assert!(!errors.is_empty());
     }
 
+    #[test]
+    fn test_ssml_validation_unclosed_tag() {
+        let ssml = r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US"><voice name="x"><prosody rate="slow">Hello</voice></speak>"#;
+        let errors = SSMLValidator::validate(ssml);
+        assert!(!errors.is_empty());
+        assert!(errors[0].contains("XML error"));
+        assert!(errors[0].contains("line 1"));
+    }
+
+    #[test]
+    fn test_ssml_validation_invalid_attribute_position() {
+        let ssml = "<speak version=\"1.0\" xmlns=\"http://www.w3.org/2001/10/synthesis\" xml:lang=\"en-US\">\n<prosody rate=\"warp\">Hello</prosody>\n</speak>";
+        let errors = SSMLValidator::validate(ssml);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("Invalid prosody rate: warp"));
+        assert!(errors[0].contains("line 2"));
+    }
+
+    #[test]
+    fn test_ssml_validation_bad_entity() {
+        let ssml = r#"<speak version="1.0" xmlns="http://www.w3.org/2001/10/synthesis" xml:lang="en-US">a &undefined; b</speak>"#;
+        let errors = SSMLValidator::validate(ssml);
+        assert!(!errors.is_empty());
+    }
+
     #[test]
     fn test_templates() {
         let result =
@@ -434,6 +2988,43 @@ mod tests {
         assert!(result.unwrap().contains("rate=\"slow\""));
     }
 
+    #[test]
+    fn test_template_placeholders() {
+        let mut values = std::collections::HashMap::new();
+        values.insert("line".to_string(), "S7 & S8".to_string());
+        values.insert("time".to_string(), "10:42".to_string());
+
+        let ssml = SSMLTemplates::create_from_custom_template(
+            "Train {line} departs at {time}",
+            &values,
+            "en-US-AriaNeural",
+        )
+        .unwrap();
+
+        assert!(ssml.contains("Train S7 &amp; S8 departs at 10:42"));
+        assert!(SSMLValidator::validate(&ssml).is_empty());
+    }
+
+    #[test]
+    fn test_template_placeholder_errors() {
+        let values = std::collections::HashMap::new();
+        assert!(SSMLTemplates::fill_placeholders("Hello {name}", &values)
+            .unwrap_err()
+            .contains("Missing placeholder value: name"));
+        assert!(SSMLTemplates::fill_placeholders("Hello {name", &values)
+            .unwrap_err()
+            .contains("Unclosed placeholder"));
+    }
+
+    #[test]
+    fn test_template_literal_braces() {
+        let values = std::collections::HashMap::new();
+        assert_eq!(
+            SSMLTemplates::fill_placeholders("a {{literal}} b", &values).unwrap(),
+            "a {literal} b"
+        );
+    }
+
     #[test]
     fn test_template_unknown() {
         let result = SSMLTemplates::create_from_template("unknown", "Hello", "en-US-AriaNeural");
@@ -18,6 +18,8 @@ pub enum TTSError {
     VoiceNotFound(String),
     #[error("Invalid configuration: {0}")]
     Config(String),
+    #[error("Audio tagging error: {0}")]
+    Tagging(String),
 }
 
 /// Voice information structure
@@ -50,9 +52,161 @@ impl Voice {
     }
 }
 
-/// Configuration for TTS client
+/// ID3 metadata written into generated MP3 files so they are organized
+/// properly in music players and audiobook apps
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AudioTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub track: Option<u32>,
+    /// ISO 639-2 language code (e.g., "eng")
+    pub language: Option<String>,
+    /// Embedded lyrics, typically the synthesized text
+    pub lyrics: Option<String>,
+}
+
+/// Timing metadata for one spoken word, as reported by the synthesis service
+/// or estimated locally. Offsets are relative to the start of the audio.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WordBoundary {
+    pub text: String,
+    pub offset: Duration,
+    pub duration: Duration,
+}
+
+impl WordBoundary {
+    pub fn new(text: String, offset: Duration, duration: Duration) -> Self {
+        Self {
+            text,
+            offset,
+            duration,
+        }
+    }
+}
+
+/// Observer for multi-item operations (batch runs, chunked long text), so
+/// front-ends can render progress bars instead of scraping console output
+pub trait ProgressReporter: Send + Sync {
+    /// A unit of work finished: `done` of `total` items, with `bytes` of
+    /// audio produced so far across all of them
+    fn on_progress(&self, done: usize, total: usize, bytes: u64);
+}
+
+/// One caption line with its display window, assembled from word boundaries
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start: Duration,
+    pub end: Duration,
+    pub text: String,
+}
+
+/// Words per caption line before a new cue starts
+const SUBTITLE_MAX_WORDS: usize = 7;
+/// A silence gap longer than this starts a new cue even mid-line
+const SUBTITLE_MAX_GAP: Duration = Duration::from_millis(1000);
+
+/// Group word boundaries into caption cues, breaking on line length and on
+/// long pauses so captions track the speech rhythm
+pub fn boundaries_to_cues(boundaries: &[WordBoundary]) -> Vec<SubtitleCue> {
+    let mut cues: Vec<SubtitleCue> = Vec::new();
+    for word in boundaries {
+        let append = cues.last().is_some_and(|cue| {
+            cue.text.split_whitespace().count() < SUBTITLE_MAX_WORDS
+                && word.offset.saturating_sub(cue.end) <= SUBTITLE_MAX_GAP
+        });
+        if append {
+            let cue = cues.last_mut().unwrap();
+            cue.text.push(' ');
+            cue.text.push_str(&word.text);
+            cue.end = word.offset + word.duration;
+        } else {
+            cues.push(SubtitleCue {
+                start: word.offset,
+                end: word.offset + word.duration,
+                text: word.text.clone(),
+            });
+        }
+    }
+    cues
+}
+
+/// Render word boundaries as an SRT subtitle file
+pub fn boundaries_to_srt(boundaries: &[WordBoundary]) -> String {
+    let mut srt = String::new();
+    for (i, cue) in boundaries_to_cues(boundaries).iter().enumerate() {
+        srt.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_subtitle_time(cue.start, ','),
+            format_subtitle_time(cue.end, ','),
+            cue.text
+        ));
+    }
+    srt
+}
+
+/// Render word boundaries as a WebVTT subtitle file
+pub fn boundaries_to_vtt(boundaries: &[WordBoundary]) -> String {
+    let mut vtt = String::from("WEBVTT\n\n");
+    for cue in boundaries_to_cues(boundaries) {
+        vtt.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_subtitle_time(cue.start, '.'),
+            format_subtitle_time(cue.end, '.'),
+            cue.text
+        ));
+    }
+    vtt
+}
+
+/// HH:MM:SS followed by milliseconds — ',' separated for SRT, '.' for VTT
+fn format_subtitle_time(time: Duration, millis_separator: char) -> String {
+    let total = time.as_secs();
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total / 3600,
+        (total / 60) % 60,
+        total % 60,
+        millis_separator,
+        time.subsec_millis()
+    )
+}
+
+/// A named position inside synthesized audio, produced by `<bookmark>`
+/// elements in the SSML. Offsets are relative to the start of the audio.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    pub offset: Duration,
+}
+
+impl Bookmark {
+    pub fn new(name: String, offset: Duration) -> Self {
+        Self { name, offset }
+    }
+}
+
+/// Configuration for TTS client
+/// One consumer of the optional REST/gRPC servers: the bearer key it
+/// presents plus the request rate it is allowed to sustain
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ServerApiKey {
+    /// Name identifying the consumer in logs and error messages
+    pub name: String,
+    /// The bearer token the consumer presents
+    pub key: String,
+    /// Requests allowed per minute; 0 means unlimited
+    #[serde(default)]
+    pub requests_per_minute: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct TTSConfig {
+    /// Config format version, bumped when fields are renamed or change
+    /// shape; older files are migrated on load
+    #[serde(default = "default_config_version")]
+    pub version: u32,
     pub default_voice: String,
     pub output_format: String,
     pub output_directory: String,
@@ -66,11 +220,40 @@ pub struct TTSConfig {
     pub ssml: bool,
     pub batch_size: usize,
     pub max_concurrent: usize,
+    pub style: Option<String>,
+    pub style_degree: Option<f32>,
+    pub role: Option<String>,
+    /// User-defined voice aliases (e.g., "narrator" for "en-US-GuyNeural"),
+    /// resolved everywhere a voice name is accepted
+    #[serde(default)]
+    pub voice_aliases: std::collections::HashMap<String, String>,
+    /// Alternative synthesis endpoint URL (e.g., an Azure Speech region
+    /// endpoint); the Edge service is used when unset
+    #[serde(default)]
+    pub custom_endpoint: Option<String>,
+    /// Name of the OS keyring entry holding the Azure subscription key.
+    /// Only the entry name lives in the config file — the key itself is
+    /// stored and retrieved via [`crate::config_manager::SecretStore`]
+    #[serde(default)]
+    pub azure_key_secret: Option<String>,
+    /// Name of the OS keyring entry holding the custom endpoint's API key
+    #[serde(default)]
+    pub custom_endpoint_key_secret: Option<String>,
+    /// API keys accepted by the optional REST/gRPC servers, each with its
+    /// own rate limit; an empty list leaves the servers open
+    #[serde(default)]
+    pub server_api_keys: Vec<ServerApiKey>,
+}
+
+fn default_config_version() -> u32 {
+    // Files without a version predate versioning and use the v1 field names
+    1
 }
 
 impl Default for TTSConfig {
     fn default() -> Self {
         Self {
+            version: Self::CONFIG_VERSION,
             default_voice: "en-US-AriaNeural".to_string(),
             output_format: "mp3".to_string(),
             output_directory: "./output".to_string(),
@@ -84,41 +267,304 @@ impl Default for TTSConfig {
             ssml: false,
             batch_size: 5,
             max_concurrent: 3,
+            style: None,
+            style_degree: None,
+            role: None,
+            voice_aliases: std::collections::HashMap::new(),
+            custom_endpoint: None,
+            azure_key_secret: None,
+            custom_endpoint_key_secret: None,
+            server_api_keys: Vec::new(),
         }
     }
 }
 
 impl TTSConfig {
-    /// Validate configuration
+    /// Validate configuration, reporting every problem found
     pub fn validate(&self) -> Result<(), TTSError> {
+        let diagnostics = self.diagnostics();
+        if diagnostics.is_empty() {
+            Ok(())
+        } else {
+            Err(TTSError::Config(diagnostics.join("; ")))
+        }
+    }
+
+    /// Check every field and return a list of diagnostics with suggestions,
+    /// rather than stopping at the first problem
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+
         if self.default_voice.is_empty() {
-            return Err(TTSError::Config(
-                "default_voice cannot be empty".to_string(),
+            diagnostics.push("default_voice cannot be empty".to_string());
+        } else if !Self::looks_like_voice_name(&self.default_voice) {
+            diagnostics.push(format!(
+                "default_voice '{}' does not look like a voice name; expected something like 'en-US-AriaNeural'",
+                self.default_voice
             ));
         }
+
+        if !["mp3", "wav", "ogg", "pcm"].contains(&self.output_format.as_str()) {
+            diagnostics.push(format!(
+                "output_format '{}' is not supported; use one of mp3, wav, ogg, pcm",
+                self.output_format
+            ));
+        }
+
+        let dir = std::path::Path::new(&self.output_directory);
+        if dir.exists() {
+            if !dir.is_dir() {
+                diagnostics.push(format!(
+                    "output_directory '{}' exists but is not a directory",
+                    self.output_directory
+                ));
+            } else if std::fs::metadata(dir)
+                .map(|m| m.permissions().readonly())
+                .unwrap_or(false)
+            {
+                diagnostics.push(format!(
+                    "output_directory '{}' is not writable",
+                    self.output_directory
+                ));
+            }
+        }
+
         if self.batch_size == 0 {
-            return Err(TTSError::Config("batch_size must be positive".to_string()));
+            diagnostics.push("batch_size must be positive".to_string());
         }
         if self.max_concurrent == 0 {
-            return Err(TTSError::Config(
-                "max_concurrent must be positive".to_string(),
+            diagnostics.push("max_concurrent must be positive".to_string());
+        }
+
+        if self.timeout.is_zero() {
+            diagnostics
+                .push("timeout of 0 never waits for the service; try 30 seconds".to_string());
+        } else if self.timeout > Duration::from_secs(600) {
+            diagnostics.push(format!(
+                "timeout of {}s is suspiciously long; synthesis normally finishes within 30s",
+                self.timeout.as_secs()
             ));
         }
-        Ok(())
+
+        if let Some(problem) = Self::check_signed_percent("rate", &self.rate, 100) {
+            diagnostics.push(problem);
+        }
+        if let Some(problem) = Self::check_signed_percent("pitch", &self.pitch, 100) {
+            diagnostics.push(problem);
+        }
+        if let Some(problem) = Self::check_volume(&self.volume) {
+            diagnostics.push(problem);
+        }
+
+        if let Some(degree) = self.style_degree {
+            if !(0.01..=2.0).contains(&degree) {
+                diagnostics.push(format!(
+                    "style_degree must be between 0.01 and 2.0, got {}",
+                    degree
+                ));
+            }
+        }
+        if self.role.is_some() && self.style.is_none() {
+            diagnostics.push("role requires style to be set".to_string());
+        }
+
+        if let Some(endpoint) = &self.custom_endpoint {
+            if !endpoint.starts_with("http://") && !endpoint.starts_with("https://") {
+                diagnostics.push(format!(
+                    "custom_endpoint '{}' must be an http(s) URL",
+                    endpoint
+                ));
+            }
+        }
+        if self.custom_endpoint_key_secret.is_some() && self.custom_endpoint.is_none() {
+            diagnostics
+                .push("custom_endpoint_key_secret requires custom_endpoint to be set".to_string());
+        }
+
+        let mut seen_keys = std::collections::HashSet::new();
+        for api_key in &self.server_api_keys {
+            if api_key.name.is_empty() {
+                diagnostics.push("server_api_keys entries need a name".to_string());
+            }
+            if api_key.key.is_empty() {
+                diagnostics.push(format!(
+                    "server_api_keys entry '{}' has an empty key",
+                    api_key.name
+                ));
+            } else if !seen_keys.insert(&api_key.key) {
+                diagnostics.push(format!(
+                    "server_api_keys entry '{}' repeats another entry's key",
+                    api_key.name
+                ));
+            }
+        }
+
+        diagnostics
+    }
+
+    fn looks_like_voice_name(voice: &str) -> bool {
+        let parts: Vec<&str> = voice.split('-').collect();
+        parts.len() >= 3
+            && parts[0].chars().all(|c| c.is_ascii_alphabetic())
+            && (2..=3).contains(&parts[0].len())
+            && parts[1].chars().all(|c| c.is_ascii_alphanumeric())
+    }
+
+    /// Rate and pitch take signed percentages like "+20%" or "-10%"
+    fn check_signed_percent(field: &str, value: &str, limit: i32) -> Option<String> {
+        let suggestion = format!(
+            "{} '{}' is invalid; use a signed percentage like '+20%' or '-10%'",
+            field, value
+        );
+        let percent = match value.strip_suffix('%') {
+            Some(percent) => percent,
+            None => return Some(suggestion),
+        };
+        match percent.trim_start_matches('+').parse::<i32>() {
+            Ok(parsed) if parsed.abs() <= limit => None,
+            Ok(parsed) => Some(format!(
+                "{} '{}%' is outside the accepted range of -{}%..+{}%",
+                field, parsed, limit, limit
+            )),
+            Err(_) => Some(suggestion),
+        }
+    }
+
+    /// Volume takes an absolute percentage like "100%" (0-200)
+    fn check_volume(value: &str) -> Option<String> {
+        let invalid = format!(
+            "volume '{}' is invalid; use an absolute percentage like '100%'",
+            value
+        );
+        match value.strip_suffix('%') {
+            Some(percent) => match percent.trim_start_matches('+').parse::<i32>() {
+                Ok(0..=200) => None,
+                Ok(parsed) => Some(format!(
+                    "volume '{}%' is outside the accepted range of 0%..200%",
+                    parsed
+                )),
+                Err(_) => Some(invalid),
+            },
+            None => Some(invalid),
+        }
+    }
+
+    /// Resolve a requested file name against `output_directory` and
+    /// `output_format`: bare names land in the configured directory, and
+    /// names without an extension get the configured format's. Paths that
+    /// already carry a directory component are used as given.
+    pub fn resolve_output_path(&self, filename: &str) -> std::path::PathBuf {
+        let mut path = std::path::PathBuf::from(filename);
+        if path.extension().is_none() {
+            path.set_extension(&self.output_format);
+        }
+        let is_bare = path.is_relative()
+            && path
+                .parent()
+                .is_none_or(|parent| parent.as_os_str().is_empty());
+        if is_bare {
+            path = std::path::Path::new(&self.output_directory).join(path);
+        }
+        path
+    }
+
+    /// Resolve a voice alias to its configured voice name; names without an
+    /// alias pass through unchanged
+    pub fn resolve_voice(&self, voice: &str) -> String {
+        self.voice_aliases
+            .get(voice)
+            .cloned()
+            .unwrap_or_else(|| voice.to_string())
+    }
+
+    /// JSON Schema describing the configuration file format, so editors can
+    /// provide completion and validation for hand-written config files
+    pub fn json_schema() -> serde_json::Value {
+        let schema = schemars::schema_for!(TTSConfig);
+        serde_json::to_value(schema).expect("schema serialization cannot fail")
     }
 
-    /// Load configuration from JSON file
+    /// Current version of the config file format
+    pub const CONFIG_VERSION: u32 = 2;
+
+    /// Load configuration from JSON file, migrating older formats forward
     pub fn from_json_file(path: &str) -> Result<Self, TTSError> {
         let content = std::fs::read_to_string(path)
             .map_err(|e| TTSError::Config(format!("Failed to read config file {}: {}", path, e)))?;
 
-        let config: TTSConfig = serde_json::from_str(&content)
+        let value: serde_json::Value = serde_json::from_str(&content)
             .map_err(|e| TTSError::Config(format!("Invalid JSON in config file: {}", e)))?;
 
+        let config = Self::from_json_value(value)?;
         config.validate()?;
         Ok(config)
     }
 
+    /// Build a config from a raw JSON value: migrate old formats, then fill
+    /// fields the file omits from the defaults instead of failing to
+    /// deserialize
+    pub fn from_json_value(value: serde_json::Value) -> Result<Self, TTSError> {
+        let migrated = Self::migrate(value)?;
+
+        let mut merged = serde_json::to_value(TTSConfig::default())
+            .map_err(|e| TTSError::Config(format!("Failed to serialize defaults: {}", e)))?;
+        if let (Some(target), serde_json::Value::Object(fields)) =
+            (merged.as_object_mut(), migrated)
+        {
+            for (field, value) in fields {
+                target.insert(field, value);
+            }
+        }
+
+        serde_json::from_value(merged)
+            .map_err(|e| TTSError::Config(format!("Invalid config: {}", e)))
+    }
+
+    /// Upgrade a config value through each version step up to
+    /// [`Self::CONFIG_VERSION`]
+    fn migrate(mut value: serde_json::Value) -> Result<serde_json::Value, TTSError> {
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1) as u32;
+        if version > Self::CONFIG_VERSION {
+            return Err(TTSError::Config(format!(
+                "Config version {} is newer than the supported version {}",
+                version,
+                Self::CONFIG_VERSION
+            )));
+        }
+
+        if version < 2 {
+            Self::migrate_v1_to_v2(&mut value);
+        }
+        value["version"] = serde_json::json!(Self::CONFIG_VERSION);
+        Ok(value)
+    }
+
+    /// v1 used short field names and a plain number of seconds for the
+    /// timeout
+    fn migrate_v1_to_v2(value: &mut serde_json::Value) {
+        let Some(fields) = value.as_object_mut() else {
+            return;
+        };
+        for (old, new) in [
+            ("voice", "default_voice"),
+            ("format", "output_format"),
+            ("output_dir", "output_directory"),
+        ] {
+            if let Some(moved) = fields.remove(old) {
+                fields.entry(new).or_insert(moved);
+            }
+        }
+        if let Some(secs) = fields.get("timeout").and_then(|t| t.as_u64()) {
+            fields.insert(
+                "timeout".to_string(),
+                serde_json::json!({ "secs": secs, "nanos": 0 }),
+            );
+        }
+    }
+
     /// Save configuration to JSON file
     pub fn to_json_file(&self, path: &str) -> Result<(), TTSError> {
         let content = serde_json::to_string_pretty(self)
@@ -153,6 +599,7 @@ struct EdgeVoiceData {
 }
 
 /// TTS Client for Microsoft Edge TTS service
+#[derive(Clone)]
 pub struct TTSClient {
     client: Client,
     config: TTSConfig,
@@ -199,8 +646,18 @@ impl TTSClient {
             self.validate_ssml(text)?;
         }
 
+        let voice = self.config.resolve_voice(voice);
+
         // Use edge-tts via command line (similar to Dart implementation)
-        self.synthesize_via_edge_tts(text, voice).await
+        self.synthesize_via_edge_tts(text, &voice).await
+    }
+
+    /// Unique scratch path for one edge-tts invocation. Concurrent
+    /// syntheses each need their own `--write-media` target, so a
+    /// timestamp is not unique enough — the first wave of a batch starts
+    /// within the same millisecond.
+    fn temp_media_path() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("tts_output_{}.mp3", uuid::Uuid::new_v4()))
     }
 
     /// Use Python edge-tts library via process execution
@@ -209,14 +666,27 @@ impl TTSClient {
         use tokio::process::Command;
 
         // Create temporary file for output (use MP3 format)
-        let temp_dir = std::env::temp_dir();
-        let temp_file = temp_dir.join(format!(
-            "tts_output_{}.mp3",
-            std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_millis()
-        ));
+        let temp_file = Self::temp_media_path();
+
+        // Forward non-default prosody settings so the configured delivery
+        // actually reaches the synthesizer
+        let mut prosody_args: Vec<String> = Vec::new();
+        if self.config.rate != "0%" {
+            prosody_args.push(format!("--rate={}", self.config.rate));
+        }
+        if self.config.pitch != "0%" {
+            prosody_args.push(format!("--pitch={}", self.config.pitch));
+        }
+        if self.config.volume != "100%" {
+            prosody_args.push(format!("--volume={}", self.config.volume));
+        }
+
+        tracing::debug!(
+            voice,
+            bytes = text.len(),
+            ?prosody_args,
+            "Invoking edge-tts for synthesis"
+        );
 
         // Try edge-tts command
         let mut cmd = Command::new("edge-tts");
@@ -228,6 +698,7 @@ impl TTSClient {
             "--write-media",
             temp_file.to_str().unwrap(),
         ])
+        .args(&prosody_args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
 
@@ -252,6 +723,7 @@ impl TTSClient {
                     "--write-media",
                     temp_file.to_str().unwrap(),
                 ])
+                .args(&prosody_args)
                 .stdout(Stdio::piped())
                 .stderr(Stdio::piped());
 
@@ -288,21 +760,101 @@ impl TTSClient {
         self.synthesize_text_with_options(ssml, voice, true).await
     }
 
-    /// Save audio data to file
+    /// Save audio data to file, transcoding when the target extension does
+    /// not match what the service returned. Bare file names are resolved
+    /// against the configured `output_directory` and `output_format` (see
+    /// [`TTSConfig::resolve_output_path`]).
     pub async fn save_audio(&self, audio_data: &[u8], filename: &str) -> Result<(), TTSError> {
+        use crate::audio_player::AudioFormat;
+
+        let path = self.config.resolve_output_path(filename);
+
         // Ensure output directory exists
-        if let Some(parent) = std::path::Path::new(filename).parent() {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
             fs::create_dir_all(parent).await?;
         }
 
-        fs::write(filename, audio_data).await?;
+        // The service returns MP3 unless the container bytes say otherwise
+        let from = if audio_data.starts_with(b"RIFF") {
+            AudioFormat::Wav
+        } else if audio_data.starts_with(b"OggS") {
+            AudioFormat::Ogg
+        } else {
+            AudioFormat::Mp3
+        };
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        let to = match extension.as_deref() {
+            Some("pcm") | Some("raw") => {
+                let info = crate::audio_processing::probe(audio_data)
+                    .map_err(|e| TTSError::Synthesis(format!("Failed to probe audio: {}", e)))?;
+                AudioFormat::Pcm {
+                    sample_rate: info.sample_rate,
+                    channels: info.channels,
+                }
+            }
+            Some(hint) => AudioFormat::from_hint(hint).unwrap_or(from),
+            None => from,
+        };
+
+        let data = crate::audio_processing::transcode(audio_data, from, to)
+            .await
+            .map_err(|e| TTSError::Synthesis(format!("Failed to transcode audio: {}", e)))?;
+        fs::write(&path, data).await?;
+        Ok(())
+    }
+
+    /// Save MP3 audio data to file and write ID3 tags so generated files are
+    /// organized properly in players
+    pub async fn save_audio_with_tags(
+        &self,
+        audio_data: &[u8],
+        filename: &str,
+        tags: &AudioTags,
+    ) -> Result<(), TTSError> {
+        use id3::TagLike;
+
+        let path = self.config.resolve_output_path(filename);
+        self.save_audio(audio_data, filename).await?;
+
+        let mut tag = id3::Tag::new();
+        if let Some(title) = &tags.title {
+            tag.set_title(title);
+        }
+        if let Some(artist) = &tags.artist {
+            tag.set_artist(artist);
+        }
+        if let Some(album) = &tags.album {
+            tag.set_album(album);
+        }
+        if let Some(track) = tags.track {
+            tag.set_track(track);
+        }
+        if let Some(language) = &tags.language {
+            tag.add_frame(id3::Frame::text("TLAN", language));
+        }
+        if let Some(lyrics) = &tags.lyrics {
+            tag.add_frame(id3::frame::Lyrics {
+                lang: tags.language.clone().unwrap_or_else(|| "und".to_string()),
+                description: String::new(),
+                text: lyrics.clone(),
+            });
+        }
+
+        tag.write_to_path(&path, id3::Version::Id3v24)
+            .map_err(|e| TTSError::Tagging(format!("Failed to write ID3 tags: {}", e)))?;
+
         Ok(())
     }
 
     /// Get all available voices from Edge TTS service
     pub async fn list_voices(&mut self) -> Result<Vec<Voice>, TTSError> {
-        if self.config.cache_voices && self.voices_cache.is_some() {
-            return Ok(self.voices_cache.as_ref().unwrap().clone());
+        if self.config.cache_voices {
+            if let Some(cached) = &self.voices_cache {
+                return Ok(cached.clone());
+            }
         }
 
         let voices_url = "https://speech.platform.bing.com/consumer/speech/synthesize/readaloud/voices/list?trustedclienttoken=6A5AA1D4EAFF4E9FB37E23D68491D6F4";
@@ -376,6 +928,47 @@ impl TTSClient {
             .build()
     }
 
+    /// Convert plain prose into SSML with heuristic pauses, applying this
+    /// client's configured prosody defaults when they differ from neutral
+    pub fn create_text_ssml(&self, text: &str, voice: &str) -> String {
+        let non_default = |value: &str, default: &str| {
+            if value == default {
+                None
+            } else {
+                Some(value.to_string())
+            }
+        };
+        let rate = non_default(&self.config.rate, "0%");
+        let pitch = non_default(&self.config.pitch, "0%");
+        let volume = non_default(&self.config.volume, "100%");
+
+        crate::ssml_utils::text_to_ssml(
+            text,
+            voice,
+            rate.as_deref(),
+            pitch.as_deref(),
+            volume.as_deref(),
+        )
+    }
+
+    /// Create SSML speaking `text` in the style configured on this client
+    /// (`style`, `style_degree`, and `role` synthesis options)
+    pub fn create_express_ssml(&self, text: &str, voice: &str, style: Option<&str>) -> String {
+        let style = style
+            .map(|s| s.to_string())
+            .or_else(|| self.config.style.clone())
+            .unwrap_or_else(|| "general".to_string());
+
+        crate::ssml_utils::SSMLBuilder::new(voice)
+            .add_express_as_with(
+                text,
+                &style,
+                self.config.style_degree,
+                self.config.role.as_deref(),
+            )
+            .build()
+    }
+
     /// Create SSML with breaks between text parts
     pub fn create_break_ssml(&self, text_parts: &[&str], voice: &str, break_time: &str) -> String {
         crate::ssml_utils::create_break_ssml(text_parts, voice, break_time)
@@ -394,6 +987,93 @@ impl TTSClient {
         }
     }
 
+    /// Upper bound on the text sent in a single synthesis request; longer
+    /// inputs are chunked at sentence boundaries
+    const MAX_CHUNK_CHARS: usize = 3000;
+
+    /// Convert text of arbitrary length to audio data, chunking it at
+    /// paragraph and sentence boundaries and concatenating the resulting
+    /// audio, since the service rejects overly long requests
+    pub async fn synthesize_long_text(
+        &self,
+        text: &str,
+        voice: &str,
+    ) -> Result<Vec<u8>, TTSError> {
+        self.synthesize_long_text_with_progress(text, voice, None)
+            .await
+    }
+
+    /// Like [`Self::synthesize_long_text`], reporting per-chunk progress to
+    /// an optional [`ProgressReporter`] so front-ends can render a bar
+    /// instead of scraping console output
+    pub async fn synthesize_long_text_with_progress(
+        &self,
+        text: &str,
+        voice: &str,
+        reporter: Option<&dyn ProgressReporter>,
+    ) -> Result<Vec<u8>, TTSError> {
+        let chunks = crate::ssml_utils::split_text(text, Self::MAX_CHUNK_CHARS);
+        if chunks.len() <= 1 {
+            let audio_data = self.synthesize_text(text, voice, None).await?;
+            if let Some(reporter) = reporter {
+                reporter.on_progress(1, 1, audio_data.len() as u64);
+            }
+            return Ok(audio_data);
+        }
+
+        let mut segments = Vec::new();
+        let mut bytes = 0u64;
+        for (i, chunk) in chunks.iter().enumerate() {
+            if reporter.is_none() {
+                tracing::info!("Synthesizing chunk {}/{}", i + 1, chunks.len());
+            }
+            let audio_data = self.synthesize_text(chunk, voice, None).await?;
+            bytes += audio_data.len() as u64;
+            if let Some(reporter) = reporter {
+                reporter.on_progress(i + 1, chunks.len(), bytes);
+            }
+            segments.push(audio_data);
+        }
+        crate::audio_processing::concat_data(&segments)
+            .map_err(|e| TTSError::Synthesis(format!("Failed to join audio chunks: {}", e)))
+    }
+
+    /// Synthesize text chunk by chunk, yielding each chunk's audio as soon
+    /// as it is ready instead of waiting for the whole input.
+    ///
+    /// Chunks split at the same boundaries as
+    /// [`Self::synthesize_long_text`], so feeding the stream to
+    /// [`crate::AudioPlayer::play_stream`] starts playback after the first
+    /// chunk while the rest is still being generated. A synthesis failure
+    /// ends the stream with the error as its final item.
+    pub fn synthesize_stream(
+        &self,
+        text: &str,
+        voice: &str,
+    ) -> impl futures_util::Stream<Item = Result<bytes::Bytes, TTSError>> + Send + 'static {
+        let client = self.clone();
+        let voice = voice.to_string();
+        let chunks = crate::ssml_utils::split_text(text, Self::MAX_CHUNK_CHARS);
+        let (tx, rx) = tokio::sync::mpsc::channel(2);
+        tokio::spawn(async move {
+            let total = chunks.len();
+            for (i, chunk) in chunks.iter().enumerate() {
+                tracing::info!("Synthesizing stream chunk {}/{}", i + 1, total);
+                let item = client
+                    .synthesize_text(chunk, &voice, None)
+                    .await
+                    .map(bytes::Bytes::from);
+                let failed = item.is_err();
+                if tx.send(item).await.is_err() || failed {
+                    break;
+                }
+            }
+        });
+        futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        })
+    }
+
     /// Convert multiple texts to audio data using specified voice
     pub async fn batch_synthesize_text(
         &self,
@@ -404,11 +1084,11 @@ impl TTSClient {
         let mut results = Vec::new();
 
         for (i, text) in texts.iter().enumerate() {
-            println!(
+            tracing::info!(
                 "Processing batch item {}/{}: {}...",
                 i + 1,
                 texts.len(),
-                &text[..text.len().min(50)]
+                text.chars().take(50).collect::<String>()
             );
             match self
                 .synthesize_text_with_options(text, voice, use_ssml)
@@ -428,45 +1108,44 @@ impl TTSClient {
         Ok(results)
     }
 
-    /// Convert multiple texts to audio data concurrently using specified voice
-    /// Note: This is a simplified implementation for demonstration
+    /// Convert multiple texts to audio data concurrently using the
+    /// specified voice, keeping at most `max_concurrent` syntheses in
+    /// flight. Results come back in input order; the first failure aborts
+    /// the remaining items.
     pub async fn batch_synthesize_concurrent(
         &self,
         texts: &[&str],
         voice: &str,
         use_ssml: bool,
-        _max_concurrent: usize,
+        max_concurrent: usize,
     ) -> Result<Vec<Vec<u8>>, TTSError> {
-        // For simplicity, we'll process sequentially but with async/await
-        // In a real implementation, you would use proper concurrent processing with Arc<Self>
-        let mut results = Vec::new();
+        use futures_util::stream::{self, StreamExt, TryStreamExt};
 
-        for (i, text) in texts.iter().enumerate() {
-            println!(
+        let total = texts.len();
+        stream::iter(texts.iter().enumerate().map(|(i, text)| async move {
+            tracing::info!(
                 "Processing concurrent item {}/{}: {}...",
                 i + 1,
-                texts.len(),
-                &text[..text.len().min(50)]
+                total,
+                text.chars().take(50).collect::<String>()
             );
-            match self
-                .synthesize_text_with_options(text, voice, use_ssml)
+            self.synthesize_text_with_options(text, voice, use_ssml)
                 .await
-            {
-                Ok(audio_data) => results.push(audio_data),
-                Err(e) => {
-                    return Err(TTSError::Synthesis(format!(
+                .map_err(|e| {
+                    TTSError::Synthesis(format!(
                         "Failed to synthesize concurrent item {}: {}",
                         i + 1,
                         e
-                    )))
-                }
-            }
-        }
-
-        Ok(results)
+                    ))
+                })
+        }))
+        .buffered(max_concurrent.max(1))
+        .try_collect()
+        .await
     }
 
-    /// Save multiple audio data to files
+    /// Save multiple audio data to files; the resolved paths honor
+    /// `output_directory` and `output_format` like [`Self::save_audio`]
     pub async fn batch_save_audio(
         &self,
         audio_data_list: &[Vec<u8>],
@@ -475,12 +1154,16 @@ impl TTSClient {
         let mut saved_files = Vec::new();
 
         for (i, audio_data) in audio_data_list.iter().enumerate() {
-            let filename = filename_template.replace("{}", &(i + 1).to_string());
+            let filename = self
+                .config
+                .resolve_output_path(&filename_template.replace("{}", &(i + 1).to_string()))
+                .to_string_lossy()
+                .into_owned();
 
             match self.save_audio(audio_data, &filename).await {
                 Ok(_) => {
-                    saved_files.push(filename.clone());
-                    println!("Saved batch item {}: {}", i + 1, filename);
+                    tracing::info!("Saved batch item {}: {}", i + 1, filename);
+                    saved_files.push(filename);
                 }
                 Err(e) => {
                     return Err(TTSError::Synthesis(format!(
@@ -538,6 +1221,177 @@ mod tests {
         assert_eq!(config.max_retries, 3);
     }
 
+    #[test]
+    fn test_config_migration_from_v1() {
+        let old = serde_json::json!({
+            "voice": "en-GB-SoniaNeural",
+            "format": "wav",
+            "output_dir": "./old_output",
+            "timeout": 45
+        });
+
+        let config = TTSConfig::from_json_value(old).unwrap();
+        assert_eq!(config.version, TTSConfig::CONFIG_VERSION);
+        assert_eq!(config.default_voice, "en-GB-SoniaNeural");
+        assert_eq!(config.output_format, "wav");
+        assert_eq!(config.output_directory, "./old_output");
+        assert_eq!(config.timeout, Duration::from_secs(45));
+        // Fields the old file does not mention come from the defaults
+        assert_eq!(config.batch_size, 5);
+    }
+
+    #[test]
+    fn test_config_rejects_future_version() {
+        let future = serde_json::json!({ "version": 99 });
+        assert!(TTSConfig::from_json_value(future).is_err());
+    }
+
+    #[test]
+    fn test_boundaries_to_cues_breaks_on_gaps() {
+        let word = |text: &str, start_ms: u64| {
+            WordBoundary::new(
+                text.to_string(),
+                Duration::from_millis(start_ms),
+                Duration::from_millis(300),
+            )
+        };
+        let boundaries = vec![word("Hello", 0), word("there.", 400), word("Goodbye.", 3000)];
+
+        let cues = boundaries_to_cues(&boundaries);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "Hello there.");
+        assert_eq!(cues[0].end, Duration::from_millis(700));
+        assert_eq!(cues[1].text, "Goodbye.");
+    }
+
+    #[test]
+    fn test_subtitle_rendering() {
+        let boundaries = vec![WordBoundary::new(
+            "Hello".to_string(),
+            Duration::from_millis(500),
+            Duration::from_millis(1700),
+        )];
+
+        let srt = boundaries_to_srt(&boundaries);
+        assert!(srt.starts_with("1\n00:00:00,500 --> 00:00:02,200\nHello\n"));
+
+        let vtt = boundaries_to_vtt(&boundaries);
+        assert!(vtt.starts_with("WEBVTT\n\n00:00:00.500 --> 00:00:02.200\nHello\n"));
+    }
+
+    #[test]
+    fn test_resolve_output_path() {
+        let config = TTSConfig {
+            output_directory: "./audio".to_string(),
+            output_format: "wav".to_string(),
+            ..TTSConfig::default()
+        };
+
+        // Bare names pick up both the directory and the format extension
+        assert_eq!(
+            config.resolve_output_path("greeting"),
+            std::path::PathBuf::from("./audio/greeting.wav")
+        );
+        // An explicit extension is kept
+        assert_eq!(
+            config.resolve_output_path("greeting.mp3"),
+            std::path::PathBuf::from("./audio/greeting.mp3")
+        );
+        // Paths with a directory component are used as given
+        assert_eq!(
+            config.resolve_output_path("custom/dir/greeting.mp3"),
+            std::path::PathBuf::from("custom/dir/greeting.mp3")
+        );
+        assert_eq!(
+            config.resolve_output_path("/tmp/greeting"),
+            std::path::PathBuf::from("/tmp/greeting.wav")
+        );
+    }
+
+    #[test]
+    fn test_voice_alias_resolution() {
+        let mut config = TTSConfig::default();
+        config
+            .voice_aliases
+            .insert("narrator".to_string(), "en-US-GuyNeural".to_string());
+
+        assert_eq!(config.resolve_voice("narrator"), "en-US-GuyNeural");
+        assert_eq!(config.resolve_voice("en-US-AriaNeural"), "en-US-AriaNeural");
+    }
+
+    #[test]
+    fn test_config_diagnostics_clean_default() {
+        assert!(TTSConfig::default().diagnostics().is_empty());
+    }
+
+    #[test]
+    fn test_config_diagnostics_collects_all_problems() {
+        let config = TTSConfig {
+            default_voice: "Aria".to_string(),
+            output_format: "flac".to_string(),
+            rate: "fast".to_string(),
+            volume: "500%".to_string(),
+            timeout: Duration::ZERO,
+            ..TTSConfig::default()
+        };
+
+        let diagnostics = config.diagnostics();
+        assert_eq!(diagnostics.len(), 5);
+        assert!(diagnostics[0].contains("en-US-AriaNeural"));
+        assert!(diagnostics[1].contains("mp3, wav, ogg"));
+        assert!(diagnostics.iter().any(|d| d.contains("signed percentage")));
+        assert!(diagnostics.iter().any(|d| d.contains("0%..200%")));
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_temp_media_paths_are_unique() {
+        // Concurrent batch jobs grab their scratch paths within the same
+        // millisecond, so uniqueness must not depend on the clock
+        let paths: std::collections::HashSet<_> =
+            (0..100).map(|_| TTSClient::temp_media_path()).collect();
+        assert_eq!(paths.len(), 100);
+    }
+
+    #[test]
+    fn test_tts_config_json_schema() {
+        let schema = TTSConfig::json_schema();
+        let properties = schema["properties"].as_object().unwrap();
+        assert!(properties.contains_key("default_voice"));
+        assert!(properties.contains_key("rate"));
+        assert!(properties.contains_key("style_degree"));
+    }
+
+    #[tokio::test]
+    async fn test_save_audio_with_tags() {
+        use id3::TagLike;
+
+        let client = TTSClient::new(None);
+        let path = std::env::temp_dir().join(format!("tts_tags_{}.mp3", uuid::Uuid::new_v4()));
+        let path_str = path.to_str().unwrap();
+
+        let tags = AudioTags {
+            title: Some("Chapter 1".to_string()),
+            artist: Some("Aria".to_string()),
+            track: Some(1),
+            language: Some("eng".to_string()),
+            lyrics: Some("Hello, world!".to_string()),
+            ..AudioTags::default()
+        };
+
+        client
+            .save_audio_with_tags(b"not really mp3 data", path_str, &tags)
+            .await
+            .unwrap();
+
+        let tag = id3::Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.title(), Some("Chapter 1"));
+        assert_eq!(tag.artist(), Some("Aria"));
+        assert_eq!(tag.track(), Some(1));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[tokio::test]
     async fn test_tts_client_creation() {
         let client = TTSClient::new(None);